@@ -0,0 +1,36 @@
+﻿use crate::ArrayLayout;
+use std::iter::zip;
+
+impl<const N: usize> ArrayLayout<N> {
+    /// 已知基地址按 `base_align` 字节对齐时，计算所有元素地址共同保证的对齐，
+    /// 即 `base_align`、`element_size`、偏移和长度大于 1 的阶的步长的最大公约数。
+    ///
+    /// ```rust
+    /// # use ndarray_layout::ArrayLayout;
+    /// let layout = ArrayLayout::<3>::new(&[2, 3, 4], &[96, -32, 8], 64);
+    /// assert_eq!(layout.alignment(64, 8), 8);
+    /// assert_eq!(layout.index(2, 0).alignment(64, 32), 32);
+    /// assert_eq!(layout.index(2, 0).alignment(64, 8), 8);
+    /// assert_eq!(layout.index(2, 0).alignment(16, 32), 16);
+    /// ```
+    pub fn alignment(&self, base_align: usize, element_size: usize) -> usize {
+        assert!(
+            base_align.is_power_of_two(),
+            "base alignment must be a power of two"
+        );
+        zip(self.shape(), self.strides())
+            .filter(|(&d, _)| d > 1)
+            .map(|(_, &s)| s.unsigned_abs())
+            .fold(
+                gcd(gcd(base_align, element_size), self.offset().unsigned_abs()),
+                gcd,
+            )
+    }
+}
+
+fn gcd(mut a: usize, mut b: usize) -> usize {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
@@ -0,0 +1,51 @@
+﻿use crate::ArrayLayout;
+
+/// 越界错误。
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum BoundsError {
+    /// 布局访问了负数偏移。
+    NegativeOffset {
+        /// 可达的最小偏移。
+        offset: isize,
+    },
+    /// 布局访问超出了缓冲区末尾。
+    OutOfRange {
+        /// 访问的最后一个元素的结束位置。
+        end: usize,
+        /// 缓冲区的长度。
+        len: usize,
+    },
+}
+
+impl<const N: usize> ArrayLayout<N> {
+    /// 检查布局可达的所有元素是否都位于长度为 `len` 字节的缓冲区 `[0, len)` 范围内。
+    ///
+    /// ```rust
+    /// # use ndarray_layout::{ArrayLayout, BoundsError};
+    /// let layout = ArrayLayout::<3>::new(&[2, 3], &[12, -4], 8);
+    /// assert_eq!(layout.check_bounds(24, 4), Ok(()));
+    /// assert_eq!(
+    ///     layout.check_bounds(20, 4),
+    ///     Err(BoundsError::OutOfRange { end: 24, len: 20 })
+    /// );
+    /// assert_eq!(
+    ///     ArrayLayout::<3>::new(&[2, 3], &[12, -4], 4).check_bounds(24, 4),
+    ///     Err(BoundsError::NegativeOffset { offset: -4 })
+    /// );
+    /// ```
+    pub fn check_bounds(&self, len: usize, element_size: usize) -> Result<(), BoundsError> {
+        if self.is_empty() {
+            return Ok(());
+        }
+        let range = self.data_range();
+        let offset = *range.start();
+        if offset < 0 {
+            return Err(BoundsError::NegativeOffset { offset });
+        }
+        let end = *range.end() as usize + element_size;
+        if end > len {
+            return Err(BoundsError::OutOfRange { end, len });
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,82 @@
+﻿use crate::ArrayLayout;
+use std::collections::HashSet;
+
+/// 缓存行占用的估计结果。
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct CacheFootprint {
+    /// 完整遍历访问的不同缓存行数量。
+    pub lines: usize,
+    /// 沿最内层阶相邻两次访问共享缓存行的次数。
+    pub shared: usize,
+}
+
+impl<const N: usize> ArrayLayout<N> {
+    /// 估计按逻辑顺序完整遍历张量时的缓存行占用，偏移按字节计算并假设偏移 0 位于缓存行起始处。
+    ///
+    /// 计算需要遍历所有元素，复杂度与元素数量成正比。
+    ///
+    /// ```rust
+    /// # use ndarray_layout::{ArrayLayout, CacheFootprint, Endian};
+    /// let layout = ArrayLayout::<2>::new_contiguous(&[4, 32], Endian::BigEndian, 4);
+    /// assert_eq!(
+    ///     layout.cache_footprint(4, 64),
+    ///     CacheFootprint { lines: 8, shared: 120 }
+    /// );
+    ///
+    /// let layout = layout.transpose(&[1, 0]);
+    /// assert_eq!(
+    ///     layout.cache_footprint(4, 64),
+    ///     CacheFootprint { lines: 8, shared: 0 }
+    /// );
+    /// ```
+    pub fn cache_footprint(&self, element_size: usize, line_size: usize) -> CacheFootprint {
+        assert!(element_size > 0 && line_size > 0);
+        let mut ans = CacheFootprint {
+            lines: 0,
+            shared: 0,
+        };
+        if self.is_empty() {
+            return ans;
+        }
+
+        let shape = self.shape();
+        let strides = self.strides();
+        let line = line_size as isize;
+        let lines_of = |offset: isize| {
+            offset.div_euclid(line)..=(offset + element_size as isize - 1).div_euclid(line)
+        };
+
+        let mut seen = HashSet::new();
+        let mut index = vec![0; shape.len()];
+        let mut offset = self.offset();
+        let mut last = None::<isize>;
+        loop {
+            let lines = lines_of(offset);
+            if let Some(last) = last {
+                let prev = lines_of(last);
+                if prev.start() <= lines.end() && lines.start() <= prev.end() {
+                    ans.shared += 1;
+                }
+            }
+            seen.extend(lines);
+
+            // 按行优先顺序前进到下一个元素，只有最内层阶的相邻访问记为共享
+            let mut axis = shape.len();
+            loop {
+                if axis == 0 {
+                    ans.lines = seen.len();
+                    return ans;
+                }
+                axis -= 1;
+                index[axis] += 1;
+                offset += strides[axis];
+                if index[axis] < shape[axis] {
+                    break;
+                }
+                offset -= strides[axis] * shape[axis] as isize;
+                index[axis] = 0;
+            }
+            last = (axis + 1 == shape.len()).then_some(offset - strides[axis]);
+        }
+    }
+}
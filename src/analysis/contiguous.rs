@@ -0,0 +1,114 @@
+﻿use crate::ArrayLayout;
+
+impl<const N: usize> ArrayLayout<N> {
+    /// 判断张量是否按行优先（大端）紧密排布，即最后一阶步长为 `element_size`，每一阶的步长等于后一阶的步长乘以其长度。
+    /// 长度为 1 的阶不影响判断，空张量总是紧密排布的。
+    ///
+    /// ```rust
+    /// # use ndarray_layout::{ArrayLayout, Endian};
+    /// let layout = ArrayLayout::<3>::new_contiguous(&[2, 3, 4], Endian::BigEndian, 4);
+    /// assert!(layout.is_c_contiguous(4));
+    /// assert!(!layout.is_f_contiguous(4));
+    /// assert!(layout.is_contiguous(4));
+    /// ```
+    #[inline]
+    pub fn is_c_contiguous(&self, element_size: usize) -> bool {
+        let dims = self.shape().iter().zip(self.strides()).rev();
+        is_packed(dims, element_size)
+    }
+
+    /// 判断张量是否按列优先（小端）紧密排布，即第一阶步长为 `element_size`，每一阶的步长等于前一阶的步长乘以其长度。
+    /// 长度为 1 的阶不影响判断，空张量总是紧密排布的。
+    ///
+    /// ```rust
+    /// # use ndarray_layout::{ArrayLayout, Endian};
+    /// let layout = ArrayLayout::<3>::new_contiguous(&[2, 3, 4], Endian::LittleEndian, 4);
+    /// assert!(!layout.is_c_contiguous(4));
+    /// assert!(layout.is_f_contiguous(4));
+    /// assert!(layout.is_contiguous(4));
+    /// ```
+    #[inline]
+    pub fn is_f_contiguous(&self, element_size: usize) -> bool {
+        let dims = self.shape().iter().zip(self.strides());
+        is_packed(dims, element_size)
+    }
+
+    /// 计算最内层紧密排布的元素数量，即从最后一阶开始连续满足行优先紧密排布的阶所覆盖的元素数，
+    /// 同时返回这些阶的数量。
+    ///
+    /// ```rust
+    /// # use ndarray_layout::ArrayLayout;
+    /// let layout = ArrayLayout::<4>::new(&[2, 3, 4, 1], &[64, 16, 4, 4], 0);
+    /// assert_eq!(layout.contiguous_run(4), (12, 3));
+    /// assert_eq!(layout.contiguous_run(2), (1, 1));
+    /// ```
+    pub fn contiguous_run(&self, element_size: usize) -> (usize, usize) {
+        let mut expect = element_size as isize;
+        let mut run = 1;
+        let mut axes = 0;
+        for (&d, &s) in self.shape().iter().zip(self.strides()).rev() {
+            if d != 1 {
+                if s != expect {
+                    break;
+                }
+                run *= d;
+                expect *= d as isize;
+            }
+            axes += 1;
+        }
+        (run, axes)
+    }
+
+    /// 判断张量是否以任意阶顺序紧密排布，即存在一种阶的排列使张量按行优先紧密排布。
+    ///
+    /// ```rust
+    /// # use ndarray_layout::ArrayLayout;
+    /// let layout = ArrayLayout::<3>::new(&[2, 3, 4], &[4, 32, 8], 0);
+    /// assert!(!layout.is_c_contiguous(4));
+    /// assert!(!layout.is_f_contiguous(4));
+    /// assert!(layout.is_contiguous(4));
+    /// ```
+    pub fn is_contiguous(&self, element_size: usize) -> bool {
+        let mut dims = self.shape().iter().zip(self.strides()).collect::<Vec<_>>();
+        dims.sort_unstable_by_key(|(_, &s)| s);
+        is_packed(dims.into_iter(), element_size)
+    }
+
+    /// 判断张量是否恰好覆盖 [`data_range`](Self::data_range) 范围内每个字节一次，阶的顺序和步长的符号均不影响判断。
+    /// 满足此条件时，可以直接拷贝整个数据范围。
+    ///
+    /// ```rust
+    /// # use ndarray_layout::ArrayLayout;
+    /// let layout = ArrayLayout::<3>::new(&[2, 3, 4], &[4, -32, 8], 64);
+    /// assert!(!layout.is_contiguous(4));
+    /// assert!(layout.is_dense(4));
+    /// assert!(!layout.is_dense(2));
+    /// ```
+    pub fn is_dense(&self, element_size: usize) -> bool {
+        let abs = self.strides().iter().map(|s| s.abs()).collect::<Vec<_>>();
+        let mut dims = self.shape().iter().zip(&abs).collect::<Vec<_>>();
+        dims.sort_unstable_by_key(|(_, &s)| s);
+        is_packed(dims.into_iter(), element_size)
+    }
+}
+
+/// 判断从内到外排列的阶是否紧密排布。
+fn is_packed<'a>(
+    dims: impl Iterator<Item = (&'a usize, &'a isize)> + Clone,
+    element_size: usize,
+) -> bool {
+    if dims.clone().any(|(&d, _)| d == 0) {
+        return true;
+    }
+    let mut expect = element_size as isize;
+    for (&d, &s) in dims {
+        if d == 1 {
+            continue;
+        }
+        if s != expect {
+            return false;
+        }
+        expect *= d as isize;
+    }
+    true
+}
@@ -0,0 +1,105 @@
+﻿use crate::ArrayLayout;
+use std::iter::zip;
+
+impl<const N: usize> ArrayLayout<N> {
+    /// 判断布局是否为单射，即不同的多维索引总是对应不同的偏移。
+    ///
+    /// 两个索引对应同一偏移，等价于存在不全为 0 的整数 `δ`，满足 `|δ[i]| < shape[i]` 且 `Σ δ[i] * strides[i] = 0`。
+    /// 若步长绝对值排序后每一阶的步长都大于更小的步长覆盖的跨度，布局显然是单射的；
+    /// 否则按步长从大到小搜索 `δ`，并以剩余阶可达的范围剪枝。
+    ///
+    /// ```rust
+    /// # use ndarray_layout::ArrayLayout;
+    /// assert!(ArrayLayout::<3>::new(&[2, 3, 4], &[12, -4, 1], 0).is_injective());
+    /// assert!(!ArrayLayout::<3>::new(&[2, 3, 4], &[12, 0, 1], 0).is_injective());
+    /// // 索引 [2, 0] 和 [0, 3] 都对应偏移 6
+    /// assert!(!ArrayLayout::<3>::new(&[3, 4], &[3, 2], 0).is_injective());
+    /// assert!(ArrayLayout::<3>::new(&[4, 3], &[3, 2], 0).is_injective());
+    /// // 步长交错但互不重叠
+    /// assert!(ArrayLayout::<3>::new(&[2, 2, 2], &[5, 2, 1], 0).is_injective());
+    /// ```
+    pub fn is_injective(&self) -> bool {
+        let shape = self.shape();
+        if shape.contains(&0) {
+            return true;
+        }
+
+        let mut dims = zip(shape, self.strides())
+            .filter(|(&d, _)| d > 1)
+            .map(|(&d, &s)| (d - 1, s.unsigned_abs()))
+            .collect::<Vec<_>>();
+        if dims.iter().any(|&(_, s)| s == 0) {
+            return false;
+        }
+        dims.sort_unstable_by_key(|&(_, s)| s);
+
+        // 充分条件：嵌套排布
+        let mut span = 0;
+        if dims.iter().all(|&(d, s)| {
+            let ans = s > span;
+            span += d * s;
+            ans
+        }) {
+            return true;
+        }
+
+        // ranges[k] 是第 k 阶之后所有阶可达范围的半径
+        dims.reverse();
+        let mut ranges = vec![0; dims.len() + 1];
+        for k in (0..dims.len()).rev() {
+            ranges[k] = ranges[k + 1] + dims[k].0 * dims[k].1;
+        }
+
+        fn search(
+            k: usize,
+            rel: isize,
+            nonzero: bool,
+            dims: &[(usize, usize)],
+            ranges: &[usize],
+        ) -> bool {
+            let Some(&(d, s)) = dims.get(k) else {
+                return nonzero && rel == 0;
+            };
+            let (d, s) = (d as isize, s as isize);
+            let r = ranges[k + 1] as isize;
+            // |rel + δ * s| <= r
+            let lo = (-r - rel).div_euclid(s) + ((-r - rel).rem_euclid(s) != 0) as isize;
+            let hi = (r - rel).div_euclid(s);
+            // 第一个非零的 δ 取正数即可
+            let lo = lo.max(if nonzero { -d } else { 0 });
+            let hi = hi.min(d);
+            (lo..=hi)
+                .any(|delta| search(k + 1, rel + delta * s, nonzero || delta != 0, dims, ranges))
+        }
+
+        !search(0, 0, false, &dims, &ranges)
+    }
+}
+
+#[test]
+fn test() {
+    use std::collections::HashSet;
+
+    let strides = [-7, -3, -2, 0, 1, 2, 3, 5, 6];
+    for &d0 in &[1, 2, 3] {
+        for &d1 in &[2, 3, 4] {
+            for &s0 in &strides {
+                for &s1 in &strides {
+                    for &s2 in &[1, 4] {
+                        let layout = ArrayLayout::<3>::new(&[d0, d1, 3], &[s0, s1, s2], 0);
+                        let mut offsets = HashSet::new();
+                        let mut injective = true;
+                        for i in 0..d0 {
+                            for j in 0..d1 {
+                                for k in 0..3 {
+                                    injective &= offsets.insert(layout.offset_of(&[i, j, k]));
+                                }
+                            }
+                        }
+                        assert_eq!(layout.is_injective(), injective, "{d0} {d1} {s0} {s1} {s2}");
+                    }
+                }
+            }
+        }
+    }
+}
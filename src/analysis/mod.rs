@@ -0,0 +1,10 @@
+﻿mod alignment;
+mod bounds;
+mod cache;
+mod contiguous;
+mod injective;
+mod offset;
+mod order;
+
+pub use bounds::BoundsError;
+pub use cache::CacheFootprint;
@@ -0,0 +1,197 @@
+﻿use crate::ArrayLayout;
+use std::iter::zip;
+
+impl<const N: usize> ArrayLayout<N> {
+    /// 计算多维索引对应的偏移，即 `offset + Σ indices[i] * strides[i]`。
+    ///
+    /// ```rust
+    /// # use ndarray_layout::ArrayLayout;
+    /// let layout = ArrayLayout::<3>::new(&[2, 3, 4], &[12, -4, 1], 20);
+    /// assert_eq!(layout.offset_of(&[1, 2, 3]), 27);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// 索引的数量与阶数不同或任何索引越界时 panic。
+    pub fn offset_of(&self, indices: &[usize]) -> isize {
+        let content = self.content();
+        let shape = content.shape();
+        assert_eq!(
+            indices.len(),
+            shape.len(),
+            "indices and shape must have the same length"
+        );
+        for (axis, (&i, &d)) in zip(indices, shape).enumerate() {
+            assert!(i < d, "index {i} out of range {d} at axis {axis}");
+        }
+        self.offset_of_unchecked(indices)
+    }
+
+    /// 计算多维索引对应的偏移，不检查索引是否越界。
+    #[inline]
+    pub fn offset_of_unchecked(&self, indices: &[usize]) -> isize {
+        let content = self.content();
+        debug_assert_eq!(indices.len(), content.shape().len());
+        zip(indices, content.strides()).fold(content.offset(), |acc, (&i, &s)| acc + i as isize * s)
+    }
+
+    /// 判断两个布局是否形状相同且每个索引都映射到相同的偏移，忽略长度为 1 的阶的步长等不影响映射的差异。
+    ///
+    /// ```rust
+    /// # use ndarray_layout::ArrayLayout;
+    /// let a = ArrayLayout::<3>::new(&[2, 1, 4], &[4, 4, 1], 0);
+    /// let b = ArrayLayout::<3>::new(&[2, 1, 4], &[4, 0, 1], 0);
+    /// assert!(a != b);
+    /// assert!(a.maps_identically(&b));
+    ///
+    /// let c = ArrayLayout::<3>::new(&[2, 0, 4], &[4, 4, 1], 0);
+    /// let d = ArrayLayout::<3>::new(&[2, 0, 4], &[1, 2, 3], 5);
+    /// assert!(c.maps_identically(&d));
+    /// ```
+    pub fn maps_identically<const M: usize>(&self, other: &ArrayLayout<M>) -> bool {
+        let shape = self.shape();
+        if shape != other.shape() {
+            return false;
+        }
+        if shape.contains(&0) {
+            return true;
+        }
+        self.offset() == other.offset()
+            && zip(shape, zip(self.strides(), other.strides())).all(|(&d, (a, b))| d == 1 || a == b)
+    }
+
+    /// 计算多维索引按行优先顺序展平后的线性索引，与步长无关。
+    ///
+    /// ```rust
+    /// # use ndarray_layout::ArrayLayout;
+    /// let layout = ArrayLayout::<3>::new(&[2, 3, 4], &[1, 2, 6], 0);
+    /// assert_eq!(layout.ravel_index(&[1, 2, 3]), 23);
+    /// assert_eq!(layout.unravel_index(23), [1, 2, 3]);
+    /// ```
+    pub fn ravel_index(&self, indices: &[usize]) -> usize {
+        let shape = self.shape();
+        assert_eq!(
+            indices.len(),
+            shape.len(),
+            "indices and shape must have the same length"
+        );
+        zip(indices, shape)
+            .enumerate()
+            .fold(0, |acc, (axis, (&i, &d))| {
+                assert!(i < d, "index {i} out of range {d} at axis {axis}");
+                acc * d + i
+            })
+    }
+
+    /// 将行优先顺序的线性索引还原为多维索引，与步长无关。
+    pub fn unravel_index(&self, mut linear: usize) -> Vec<usize> {
+        let shape = self.shape();
+        assert!(
+            linear < self.num_elements(),
+            "linear index {linear} out of range {}",
+            self.num_elements()
+        );
+        let mut ans = vec![0; shape.len()];
+        for (i, &d) in shape.iter().enumerate().rev() {
+            ans[i] = linear % d;
+            linear /= d;
+        }
+        ans
+    }
+
+    /// 计算偏移对应的多维索引，偏移不可达时返回 `None`。
+    ///
+    /// 对于单射的布局，结果是唯一的；否则返回其中一个满足条件的索引。
+    ///
+    /// ```rust
+    /// # use ndarray_layout::ArrayLayout;
+    /// let layout = ArrayLayout::<3>::new(&[2, 3, 4], &[1, -8, 2], 16);
+    /// assert_eq!(layout.indices_of(15), Some(vec![1, 1, 3]));
+    /// assert_eq!(layout.indices_of(24), None);
+    /// ```
+    pub fn indices_of(&self, offset: isize) -> Option<Vec<usize>> {
+        let content = self.content();
+        let shape = content.shape();
+        let strides = content.strides();
+        if shape.contains(&0) {
+            return None;
+        }
+
+        // 按步长绝对值从大到小搜索，并记录剩余阶可达的偏移范围以剪枝
+        let mut axes = (0..shape.len())
+            .filter(|&i| shape[i] > 1 && strides[i] != 0)
+            .collect::<Vec<_>>();
+        axes.sort_unstable_by_key(|&i| std::cmp::Reverse(strides[i].unsigned_abs()));
+        let mut ranges = vec![(0, 0); axes.len() + 1];
+        for k in (0..axes.len()).rev() {
+            let span = strides[axes[k]] * (shape[axes[k]] - 1) as isize;
+            let (lo, hi) = ranges[k + 1];
+            ranges[k] = (lo + span.min(0), hi + span.max(0));
+        }
+
+        fn search(
+            k: usize,
+            rel: isize,
+            axes: &[usize],
+            ranges: &[(isize, isize)],
+            shape: &[usize],
+            strides: &[isize],
+            ans: &mut [usize],
+        ) -> bool {
+            let (lo, hi) = ranges[k];
+            if rel < lo || rel > hi {
+                return false;
+            }
+            let Some(&axis) = axes.get(k) else {
+                return rel == 0;
+            };
+            let s = strides[axis];
+            let (lo, hi) = ranges[k + 1];
+            // rel - i * s ∈ [lo, hi]
+            let (a, b) = if s > 0 {
+                ((rel - hi).div_euclid(s), (rel - lo).div_euclid(s))
+            } else {
+                ((rel - lo).div_euclid(s), (rel - hi).div_euclid(s))
+            };
+            let last = shape[axis] as isize - 1;
+            for i in a.max(0)..=b.min(last) {
+                ans[axis] = i as _;
+                if search(k + 1, rel - i * s, axes, ranges, shape, strides, ans) {
+                    return true;
+                }
+            }
+            false
+        }
+
+        let mut ans = vec![0; shape.len()];
+        search(
+            0,
+            offset - content.offset(),
+            &axes,
+            &ranges,
+            shape,
+            strides,
+            &mut ans,
+        )
+        .then_some(ans)
+    }
+}
+
+#[test]
+fn test() {
+    let layout = ArrayLayout::<4>::new(&[3, 5, 2], &[4, 3, -1], 2);
+    for i in 0..3 {
+        for j in 0..5 {
+            for k in 0..2 {
+                let offset = layout.offset_of(&[i, j, k]);
+                let indices = layout.indices_of(offset).unwrap();
+                assert_eq!(layout.offset_of(&indices), offset);
+            }
+        }
+    }
+    assert_eq!(layout.indices_of(1), Some(vec![0, 0, 1]));
+    assert_eq!(layout.indices_of(-1), None);
+    assert!(ArrayLayout::<4>::new(&[2, 0], &[1, 1], 0)
+        .indices_of(0)
+        .is_none());
+}
@@ -0,0 +1,33 @@
+﻿use crate::ArrayLayout;
+use std::cmp::Reverse;
+
+impl<const N: usize> ArrayLayout<N> {
+    /// 按步长绝对值从大到小排列的阶，步长绝对值相同时序号较小的阶在前。
+    ///
+    /// ```rust
+    /// # use ndarray_layout::ArrayLayout;
+    /// let layout = ArrayLayout::<4>::new(&[2, 3, 4, 5], &[1, -20, 0, 4], 0);
+    /// assert_eq!(layout.stride_order(), [1, 3, 0, 2]);
+    /// ```
+    pub fn stride_order(&self) -> Vec<usize> {
+        let strides = self.strides();
+        let mut ans = (0..self.ndim).collect::<Vec<_>>();
+        ans.sort_by_key(|&i| Reverse(strides[i].unsigned_abs()));
+        ans
+    }
+
+    /// 判断两个布局的阶是否按相同的顺序排布步长。
+    ///
+    /// ```rust
+    /// # use ndarray_layout::{ArrayLayout, Endian};
+    /// let a = ArrayLayout::<3>::new_contiguous(&[2, 3, 4], Endian::BigEndian, 4);
+    /// let b = ArrayLayout::<3>::new_contiguous(&[5, 6, 7], Endian::BigEndian, 2);
+    /// let c = ArrayLayout::<3>::new_contiguous(&[2, 3, 4], Endian::LittleEndian, 4);
+    /// assert!(a.same_order(&b));
+    /// assert!(!a.same_order(&c));
+    /// ```
+    #[inline]
+    pub fn same_order<const M: usize>(&self, other: &ArrayLayout<M>) -> bool {
+        self.stride_order() == other.stride_order()
+    }
+}
@@ -0,0 +1,265 @@
+use crate::{ArrayLayout, Endian};
+use std::fmt::Write;
+
+/// Arrow `FixedShapeTensor` 扩展类型的元数据。
+///
+/// 每个张量存储为定长列表中的一项，按 `permutation` 重排后的物理形状行优先紧密排布。
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct FixedShapeTensor {
+    /// 逻辑形状。
+    pub shape: Vec<usize>,
+    /// 物理排布中各阶对应的逻辑阶，`None` 表示不重排。
+    pub permutation: Option<Vec<usize>>,
+    /// 各阶的名字。
+    pub dim_names: Option<Vec<String>>,
+}
+
+impl FixedShapeTensor {
+    /// 扩展类型的名字。
+    pub const EXTENSION_NAME: &'static str = "arrow.fixed_shape_tensor";
+
+    /// 单个张量的逻辑布局，偏移为 0。
+    ///
+    /// ```rust
+    /// # use ndarray_layout::FixedShapeTensor;
+    /// let tensor = FixedShapeTensor {
+    ///     shape: vec![100, 200, 500],
+    ///     permutation: Some(vec![2, 0, 1]),
+    ///     dim_names: None,
+    /// };
+    /// let layout = tensor.layout::<3>(4);
+    /// assert_eq!(layout.shape(), &[100, 200, 500]);
+    /// assert_eq!(layout.strides(), &[800, 4, 80000]);
+    /// assert_eq!(layout.to_fixed_shape_tensor(4), Some(tensor));
+    /// ```
+    pub fn layout<const N: usize>(&self, element_size: usize) -> ArrayLayout<N> {
+        let Some(perm) = &self.permutation else {
+            return ArrayLayout::new_contiguous(&self.shape, Endian::BigEndian, element_size);
+        };
+        let physical = perm.iter().map(|&i| self.shape[i]).collect::<Vec<_>>();
+        ArrayLayout::<N>::new_contiguous(&physical, Endian::BigEndian, element_size)
+            .untranspose(perm)
+    }
+
+    /// 编码为扩展类型的 JSON 元数据。
+    ///
+    /// ```rust
+    /// # use ndarray_layout::FixedShapeTensor;
+    /// let tensor = FixedShapeTensor {
+    ///     shape: vec![2, 3],
+    ///     permutation: Some(vec![1, 0]),
+    ///     dim_names: Some(vec!["H".into(), "W".into()]),
+    /// };
+    /// let metadata = tensor.metadata();
+    /// assert_eq!(metadata, r#"{"shape":[2,3],"dim_names":["H","W"],"permutation":[1,0]}"#);
+    /// assert_eq!(FixedShapeTensor::parse_metadata(&metadata), Some(tensor));
+    /// ```
+    pub fn metadata(&self) -> String {
+        fn list<T>(ans: &mut String, items: &[T], f: impl Fn(&mut String, &T)) {
+            ans.push('[');
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    ans.push(',');
+                }
+                f(ans, item)
+            }
+            ans.push(']')
+        }
+
+        let mut ans = String::from(r#"{"shape":"#);
+        list(&mut ans, &self.shape, |s, d| {
+            let _ = write!(s, "{d}");
+        });
+        if let Some(names) = &self.dim_names {
+            ans.push_str(r#","dim_names":"#);
+            list(&mut ans, names, |s, name| {
+                s.push('"');
+                for c in name.chars() {
+                    match c {
+                        '"' => s.push_str(r#"\""#),
+                        '\\' => s.push_str(r"\\"),
+                        c if c.is_control() => {
+                            let _ = write!(s, "\\u{:04x}", c as u32);
+                        }
+                        c => s.push(c),
+                    }
+                }
+                s.push('"');
+            });
+        }
+        if let Some(perm) = &self.permutation {
+            ans.push_str(r#","permutation":"#);
+            list(&mut ans, perm, |s, i| {
+                let _ = write!(s, "{i}");
+            });
+        }
+        ans.push('}');
+        ans
+    }
+
+    /// 解析扩展类型的 JSON 元数据，格式错误时返回 `None`。
+    pub fn parse_metadata(metadata: &str) -> Option<Self> {
+        let mut json = Json(metadata.trim());
+        let mut ans = Self {
+            shape: Vec::new(),
+            permutation: None,
+            dim_names: None,
+        };
+        let mut has_shape = false;
+
+        json.eat('{')?;
+        if !json.eat_if('}') {
+            loop {
+                match &*json.string()? {
+                    "shape" => {
+                        json.eat(':')?;
+                        ans.shape = json.array(Json::integer)?;
+                        has_shape = true;
+                    }
+                    "permutation" => {
+                        json.eat(':')?;
+                        ans.permutation = Some(json.array(Json::integer)?);
+                    }
+                    "dim_names" => {
+                        json.eat(':')?;
+                        ans.dim_names = Some(json.array(Json::string)?);
+                    }
+                    _ => return None,
+                }
+                if json.eat_if('}') {
+                    break;
+                }
+                json.eat(',')?;
+            }
+        }
+
+        let ndim = ans.shape.len();
+        let valid_perm = ans.permutation.as_ref().is_none_or(|perm| {
+            let mut seen = vec![false; ndim];
+            perm.len() == ndim
+                && perm
+                    .iter()
+                    .all(|&i| i < ndim && !std::mem::replace(&mut seen[i], true))
+        });
+        let valid_names = ans
+            .dim_names
+            .as_ref()
+            .is_none_or(|names| names.len() == ndim);
+        (has_shape && valid_perm && valid_names && json.0.is_empty()).then_some(ans)
+    }
+}
+
+impl<const N: usize> ArrayLayout<N> {
+    /// 将布局表示为 Arrow `FixedShapeTensor` 的元数据。
+    /// 布局必须在某种阶的排列下行优先紧密排布，偏移被忽略。
+    pub fn to_fixed_shape_tensor(&self, element_size: usize) -> Option<FixedShapeTensor> {
+        let perm = self.stride_order();
+        if !self.transpose(&perm).is_c_contiguous(element_size) {
+            return None;
+        }
+        let identity = perm.iter().enumerate().all(|(i, &p)| i == p);
+        Some(FixedShapeTensor {
+            shape: self.shape().to_vec(),
+            permutation: (!identity).then_some(perm),
+            dim_names: None,
+        })
+    }
+}
+
+/// 只支持元数据所需子集的 JSON 解析器。
+struct Json<'a>(&'a str);
+
+impl Json<'_> {
+    fn eat_if(&mut self, c: char) -> bool {
+        match self.0.trim_start().strip_prefix(c) {
+            Some(rest) => {
+                self.0 = rest.trim_start();
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn eat(&mut self, c: char) -> Option<()> {
+        self.eat_if(c).then_some(())
+    }
+
+    fn integer(&mut self) -> Option<usize> {
+        let len = self
+            .0
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(self.0.len());
+        let (digits, rest) = self.0.split_at(len);
+        self.0 = rest.trim_start();
+        digits.parse().ok()
+    }
+
+    fn string(&mut self) -> Option<String> {
+        self.eat('"')?;
+        let mut ans = String::new();
+        let mut chars = self.0.char_indices();
+        while let Some((i, c)) = chars.next() {
+            match c {
+                '"' => {
+                    self.0 = self.0[i + 1..].trim_start();
+                    return Some(ans);
+                }
+                '\\' => match chars.next()?.1 {
+                    'n' => ans.push('\n'),
+                    't' => ans.push('\t'),
+                    'r' => ans.push('\r'),
+                    c @ ('"' | '\\' | '/') => ans.push(c),
+                    'u' => {
+                        let hex = (0..4)
+                            .map(|_| chars.next().map(|(_, c)| c))
+                            .collect::<Option<String>>()?;
+                        ans.push(char::from_u32(u32::from_str_radix(&hex, 16).ok()?)?)
+                    }
+                    _ => return None,
+                },
+                c => ans.push(c),
+            }
+        }
+        None
+    }
+
+    fn array<T>(&mut self, mut item: impl FnMut(&mut Self) -> Option<T>) -> Option<Vec<T>> {
+        self.eat('[')?;
+        let mut ans = Vec::new();
+        if self.eat_if(']') {
+            return Some(ans);
+        }
+        loop {
+            ans.push(item(self)?);
+            if self.eat_if(']') {
+                return Some(ans);
+            }
+            self.eat(',')?;
+        }
+    }
+}
+
+#[test]
+fn test() {
+    let tensor = FixedShapeTensor {
+        shape: vec![2],
+        permutation: None,
+        dim_names: Some(vec!["a\"b\\c\n".into()]),
+    };
+    let metadata = tensor.metadata();
+    assert_eq!(metadata, r#"{"shape":[2],"dim_names":["a\"b\\c\u000a"]}"#);
+    assert_eq!(FixedShapeTensor::parse_metadata(&metadata), Some(tensor));
+
+    assert_eq!(
+        FixedShapeTensor::parse_metadata(r#" { "permutation" : [0, 1], "shape" : [3, 4] } "#)
+            .unwrap()
+            .shape,
+        [3, 4]
+    );
+    assert!(FixedShapeTensor::parse_metadata(r#"{"shape":[3,4],"permutation":[0,0]}"#).is_none());
+    assert!(FixedShapeTensor::parse_metadata(r#"{"permutation":[0]}"#).is_none());
+    assert!(FixedShapeTensor::parse_metadata(r#"{"shape":[3]} x"#).is_none());
+    assert!(ArrayLayout::<2>::new(&[2, 3], &[16, 4], 0)
+        .to_fixed_shape_tensor(4)
+        .is_none());
+}
@@ -0,0 +1,178 @@
+use crate::{ArrayLayout, Endian, LayoutError};
+
+/// 逐阶构造布局的构造器。
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct LayoutBuilder<const N: usize = 2> {
+    /// 已追加的阶的长度和步长，紧密排布的阶的步长在构造时确定。
+    dims: Dims<N>,
+    offset: isize,
+    endian: Endian,
+    element_size: usize,
+}
+
+impl<const N: usize> ArrayLayout<N> {
+    /// 创建元素大小为 `element_size` 的布局构造器。
+    ///
+    /// 指定步长的阶直接使用给定的步长；未指定步长的阶之间按 `endian` 的顺序紧密排布，
+    /// 最内层的步长为 `element_size`，不受指定步长的阶影响。
+    ///
+    /// ```rust
+    /// # use ndarray_layout::{ArrayLayout, Endian};
+    /// let layout = ArrayLayout::<4>::builder(4)
+    ///     .push_contiguous(2)
+    ///     .push(5, 0)
+    ///     .push_contiguous(3)
+    ///     .offset(16)
+    ///     .build()
+    ///     .unwrap();
+    /// assert_eq!(layout.shape(), &[2, 5, 3]);
+    /// assert_eq!(layout.strides(), &[12, 0, 4]);
+    /// assert_eq!(layout.offset(), 16);
+    ///
+    /// let layout = ArrayLayout::<4>::builder(4)
+    ///     .endian(Endian::LittleEndian)
+    ///     .push_contiguous(2)
+    ///     .push_contiguous(3)
+    ///     .build()
+    ///     .unwrap();
+    /// assert_eq!(layout.strides(), &[4, 8]);
+    /// ```
+    #[inline]
+    pub fn builder(element_size: usize) -> LayoutBuilder<N> {
+        LayoutBuilder {
+            dims: Dims {
+                inline: [(0, None); N],
+                len: 0,
+                spill: Vec::new(),
+            },
+            offset: 0,
+            endian: Endian::BigEndian,
+            element_size,
+        }
+    }
+}
+
+impl<const N: usize> LayoutBuilder<N> {
+    /// 追加一个指定长度和步长的阶。
+    #[inline]
+    pub fn push(mut self, extent: usize, stride: isize) -> Self {
+        self.dims.push((extent, Some(stride)));
+        self
+    }
+
+    /// 追加一个紧密排布的阶，步长在构造时确定。
+    #[inline]
+    pub fn push_contiguous(mut self, extent: usize) -> Self {
+        self.dims.push((extent, None));
+        self
+    }
+
+    /// 设置偏移。
+    #[inline]
+    pub fn offset(mut self, offset: isize) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    /// 设置紧密排布的阶的顺序，默认为大端序。
+    #[inline]
+    pub fn endian(mut self, endian: Endian) -> Self {
+        self.endian = endian;
+        self
+    }
+
+    /// 构造布局。
+    ///
+    /// 只检查紧密排布的步长是否溢出，溢出时返回错误；
+    /// 不检查指定的步长和偏移，也不检查不同的阶是否重叠。
+    ///
+    /// ```rust
+    /// # use ndarray_layout::{ArrayLayout, LayoutError};
+    /// let result = ArrayLayout::<4>::builder(4)
+    ///     .push_contiguous(2)
+    ///     .push_contiguous(usize::MAX / 2)
+    ///     .push_contiguous(4)
+    ///     .build();
+    /// assert_eq!(result.err(), Some(LayoutError::StrideOverflow { axis: 0 }));
+    /// ```
+    pub fn build(self) -> Result<ArrayLayout<N>, LayoutError> {
+        let Self {
+            dims,
+            offset,
+            endian,
+            element_size,
+        } = self;
+
+        let mut ans = ArrayLayout::with_ndim(dims.len());
+        let mut content = ans.content_mut();
+        content.set_offset(offset);
+
+        let mut mul = Some(element_size as isize);
+        let mut set = |axis: usize| -> Result<(), LayoutError> {
+            let (d, s) = dims.get(axis);
+            content.set_shape(axis, d);
+            let s = match s {
+                Some(s) => s,
+                None => {
+                    let s = mul.ok_or(LayoutError::StrideOverflow { axis })?;
+                    mul = isize::try_from(d).ok().and_then(|d| s.checked_mul(d));
+                    s
+                }
+            };
+            content.set_stride(axis, s);
+            Ok(())
+        };
+        match endian {
+            Endian::BigEndian => (0..dims.len()).rev().try_for_each(&mut set)?,
+            Endian::LittleEndian => (0..dims.len()).try_for_each(&mut set)?,
+        }
+        Ok(ans)
+    }
+}
+
+/// 构造器中的阶，不超过 `N` 阶时保存在定长数组中，不分配堆内存。
+#[derive(Clone, PartialEq, Eq, Debug)]
+struct Dims<const N: usize> {
+    inline: [(usize, Option<isize>); N],
+    len: usize,
+    spill: Vec<(usize, Option<isize>)>,
+}
+
+impl<const N: usize> Dims<N> {
+    #[inline]
+    fn push(&mut self, dim: (usize, Option<isize>)) {
+        match self.inline.get_mut(self.len) {
+            Some(slot) => *slot = dim,
+            None => self.spill.push(dim),
+        }
+        self.len += 1;
+    }
+
+    #[inline]
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    #[inline]
+    fn get(&self, axis: usize) -> (usize, Option<isize>) {
+        match self.inline.get(axis) {
+            Some(&dim) => dim,
+            None => self.spill[axis - N],
+        }
+    }
+}
+
+#[test]
+fn test() {
+    // 超出内联容量的阶保存在堆上
+    let layout = ArrayLayout::<2>::builder(4)
+        .push_contiguous(2)
+        .push(5, 0)
+        .push_contiguous(3)
+        .offset(8)
+        .build()
+        .unwrap();
+    assert_eq!(layout.shape(), &[2, 5, 3]);
+    assert_eq!(layout.strides(), &[12, 0, 4]);
+    assert_eq!(layout.offset(), 8);
+}
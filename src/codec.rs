@@ -0,0 +1,138 @@
+use crate::ArrayLayout;
+
+/// 二进制编码的版本号。
+const VERSION: u8 = 1;
+
+/// 二进制解码错误。
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum DecodeError {
+    /// 不支持的版本号。
+    UnsupportedVersion(u8),
+    /// 数据提前结束。
+    UnexpectedEnd,
+    /// 整数超出表示范围。
+    Overflow,
+    /// 编码结束后仍有多余的字节。
+    TrailingBytes(usize),
+}
+
+impl<const N: usize> ArrayLayout<N> {
+    /// 将布局编码为紧凑的二进制格式。
+    ///
+    /// 第一个字节为版本号，其后依次为阶数、偏移、形状和步长，
+    /// 每个整数以 LEB128 变长编码，有符号整数先进行 zigzag 变换。
+    ///
+    /// ```rust
+    /// # use ndarray_layout::ArrayLayout;
+    /// let layout = ArrayLayout::<3>::new(&[2, 3, 200], &[2400, -800, 4], 1600);
+    /// let bytes = layout.to_bytes();
+    /// assert_eq!(bytes, [1, 3, 0x80, 0x19, 2, 3, 0xc8, 1, 0xc0, 0x25, 0xbf, 0x0c, 8]);
+    /// assert!(ArrayLayout::<3>::from_bytes(&bytes).unwrap() == layout);
+    /// ```
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut ans = vec![VERSION];
+        write_varint(&mut ans, self.ndim as u64);
+        write_varint(&mut ans, zigzag(self.offset()));
+        for &d in self.shape() {
+            write_varint(&mut ans, d as u64);
+        }
+        for &s in self.strides() {
+            write_varint(&mut ans, zigzag(s));
+        }
+        ans
+    }
+
+    /// 从 [`to_bytes`](Self::to_bytes) 产生的二进制格式解码布局。
+    ///
+    /// ```rust
+    /// # use ndarray_layout::{ArrayLayout, DecodeError};
+    /// assert_eq!(ArrayLayout::<3>::from_bytes(&[2, 0, 0]).err(), Some(DecodeError::UnsupportedVersion(2)));
+    /// assert_eq!(ArrayLayout::<3>::from_bytes(&[1, 1, 0, 4]).err(), Some(DecodeError::UnexpectedEnd));
+    /// assert_eq!(ArrayLayout::<3>::from_bytes(&[1, 0, 0, 0]).err(), Some(DecodeError::TrailingBytes(1)));
+    /// ```
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, DecodeError> {
+        let (&version, mut bytes) = bytes.split_first().ok_or(DecodeError::UnexpectedEnd)?;
+        if version != VERSION {
+            return Err(DecodeError::UnsupportedVersion(version));
+        }
+        let ndim = read_usize(&mut bytes)?;
+        let offset = unzigzag(read_varint(&mut bytes)?)?;
+        // 每个整数至少占一个字节，先检查长度以免恶意的阶数导致过量分配
+        if bytes.len() < ndim.saturating_mul(2) {
+            return Err(DecodeError::UnexpectedEnd);
+        }
+        let shape = (0..ndim)
+            .map(|_| read_usize(&mut bytes))
+            .collect::<Result<Vec<_>, _>>()?;
+        let strides = (0..ndim)
+            .map(|_| unzigzag(read_varint(&mut bytes)?))
+            .collect::<Result<Vec<_>, _>>()?;
+        if !bytes.is_empty() {
+            return Err(DecodeError::TrailingBytes(bytes.len()));
+        }
+        Ok(Self::new(&shape, &strides, offset))
+    }
+}
+
+#[inline]
+fn zigzag(n: isize) -> u64 {
+    let n = n as i64;
+    ((n << 1) ^ (n >> 63)) as u64
+}
+
+#[inline]
+fn unzigzag(n: u64) -> Result<isize, DecodeError> {
+    let n = ((n >> 1) as i64) ^ -((n & 1) as i64);
+    isize::try_from(n).map_err(|_| DecodeError::Overflow)
+}
+
+fn write_varint(buf: &mut Vec<u8>, mut n: u64) {
+    while n >= 0x80 {
+        buf.push(n as u8 | 0x80);
+        n >>= 7;
+    }
+    buf.push(n as u8)
+}
+
+fn read_varint(bytes: &mut &[u8]) -> Result<u64, DecodeError> {
+    let mut ans = 0u64;
+    for shift in (0..64).step_by(7) {
+        let (&byte, rest) = bytes.split_first().ok_or(DecodeError::UnexpectedEnd)?;
+        *bytes = rest;
+        let bits = (byte & 0x7f) as u64;
+        if shift == 63 && bits > 1 {
+            return Err(DecodeError::Overflow);
+        }
+        ans |= bits << shift;
+        if byte & 0x80 == 0 {
+            return Ok(ans);
+        }
+    }
+    Err(DecodeError::Overflow)
+}
+
+#[inline]
+fn read_usize(bytes: &mut &[u8]) -> Result<usize, DecodeError> {
+    usize::try_from(read_varint(bytes)?).map_err(|_| DecodeError::Overflow)
+}
+
+#[test]
+fn test() {
+    for layout in [
+        ArrayLayout::<2>::new(&[], &[], -7),
+        ArrayLayout::<2>::new(&[usize::MAX, 1, 0], &[isize::MIN, isize::MAX, -1], 0),
+    ] {
+        let bytes = layout.to_bytes();
+        assert!(ArrayLayout::<2>::from_bytes(&bytes).unwrap() == layout);
+        for len in 0..bytes.len() {
+            assert!(ArrayLayout::<2>::from_bytes(&bytes[..len]).is_err());
+        }
+    }
+    assert_eq!(
+        ArrayLayout::<2>::from_bytes(&[
+            1, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x7f
+        ])
+        .err(),
+        Some(DecodeError::Overflow)
+    );
+}
@@ -0,0 +1,132 @@
+use crate::{ArrayLayout, Endian};
+
+/// 卷积或池化的填充方式。
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ConvPadding<'a> {
+    /// 不填充，只保留完整的窗口。
+    Valid,
+    /// 填充使输出长度等于输入长度除以步长向上取整，多余的填充放在后方。
+    Same,
+    /// 显式指定每个空间阶前后填充的长度。
+    Explicit(&'a [(usize, usize)]),
+}
+
+/// 卷积或池化的参数，作用于输入的最后 `kernel.len()` 个阶。
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct ConvArg<'a> {
+    /// 卷积核的形状。
+    pub kernel: &'a [usize],
+    /// 每个空间阶的步长。
+    pub stride: &'a [usize],
+    /// 每个空间阶的膨胀。
+    pub dilation: &'a [usize],
+    /// 填充方式。
+    pub padding: ConvPadding<'a>,
+}
+
+impl ConvArg<'_> {
+    /// 计算每个空间阶前后实际填充的长度。
+    pub fn pads(&self, input: &[usize]) -> Vec<(usize, usize)> {
+        let spatial = self.check(input);
+        match self.padding {
+            ConvPadding::Valid => vec![(0, 0); spatial.len()],
+            ConvPadding::Same => (0..spatial.len())
+                .map(|i| {
+                    let out = spatial[i].div_ceil(self.stride[i]);
+                    // 长度为 0 的阶没有输出，也不需要填充
+                    if out == 0 {
+                        return (0, 0);
+                    }
+                    let total =
+                        ((out - 1) * self.stride[i] + self.receptive(i)).saturating_sub(spatial[i]);
+                    (total / 2, total - total / 2)
+                })
+                .collect(),
+            ConvPadding::Explicit(pads) => {
+                assert_eq!(pads.len(), spatial.len());
+                pads.to_vec()
+            }
+        }
+    }
+
+    /// 计算输出形状，空间阶之前的阶保持不变。
+    /// [`Same`](ConvPadding::Same) 填充时长度为 0 的空间阶输出长度也为 0。
+    ///
+    /// ```rust
+    /// # use ndarray_layout::{ConvArg, ConvPadding};
+    /// let arg = ConvArg {
+    ///     kernel: &[3, 3],
+    ///     stride: &[2, 2],
+    ///     dilation: &[1, 1],
+    ///     padding: ConvPadding::Valid,
+    /// };
+    /// assert_eq!(arg.output_shape(&[1, 8, 7, 9]), [1, 8, 3, 4]);
+    ///
+    /// let arg = ConvArg {
+    ///     padding: ConvPadding::Same,
+    ///     ..arg
+    /// };
+    /// assert_eq!(arg.output_shape(&[1, 8, 7, 9]), [1, 8, 4, 5]);
+    /// assert_eq!(arg.output_shape(&[1, 8, 0, 9]), [1, 8, 0, 5]);
+    /// assert_eq!(arg.pads(&[1, 8, 0, 9]), [(0, 0), (1, 1)]);
+    /// ```
+    pub fn output_shape(&self, input: &[usize]) -> Vec<usize> {
+        let pads = self.pads(input);
+        let batch = input.len() - pads.len();
+        let mut ans = input.to_vec();
+        for (i, &(pre, post)) in pads.iter().enumerate() {
+            if input[batch + i] == 0 && self.padding == ConvPadding::Same {
+                continue;
+            }
+            let d = input[batch + i] + pre + post;
+            let receptive = self.receptive(i);
+            assert!(receptive <= d, "receptive field {receptive} exceeds {d}");
+            ans[batch + i] = (d - receptive) / self.stride[i] + 1;
+        }
+        ans
+    }
+
+    fn check<'a>(&self, input: &'a [usize]) -> &'a [usize] {
+        let n = self.kernel.len();
+        assert_eq!(self.stride.len(), n);
+        assert_eq!(self.dilation.len(), n);
+        assert!(n <= input.len());
+        assert!(self.kernel.iter().all(|&k| k > 0));
+        assert!(self.stride.iter().all(|&s| s > 0));
+        assert!(self.dilation.iter().all(|&d| d > 0));
+        &input[input.len() - n..]
+    }
+
+    #[inline]
+    fn receptive(&self, i: usize) -> usize {
+        self.dilation[i] * (self.kernel[i] - 1) + 1
+    }
+}
+
+impl<const N: usize> ArrayLayout<N> {
+    /// 计算卷积或池化的输出形状，并给出大端连续的输出布局。
+    ///
+    /// ```rust
+    /// # use ndarray_layout::{ArrayLayout, ConvArg, ConvPadding};
+    /// let input = ArrayLayout::<4>::new(&[1, 2, 5, 5], &[50, 25, 5, 1], 0);
+    /// let output = input.conv_output(
+    ///     &ConvArg {
+    ///         kernel: &[3, 3],
+    ///         stride: &[1, 1],
+    ///         dilation: &[1, 1],
+    ///         padding: ConvPadding::Explicit(&[(1, 1), (0, 0)]),
+    ///     },
+    ///     4,
+    /// );
+    /// assert_eq!(output.shape(), &[1, 2, 5, 3]);
+    /// assert_eq!(output.strides(), &[120, 60, 12, 4]);
+    /// assert_eq!(output.offset(), 0);
+    /// ```
+    pub fn conv_output(&self, arg: &ConvArg, element_size: usize) -> Self {
+        Self::new_contiguous(
+            &arg.output_shape(self.shape()),
+            Endian::BigEndian,
+            element_size,
+        )
+    }
+}
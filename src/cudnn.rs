@@ -0,0 +1,92 @@
+use crate::ArrayLayout;
+
+/// cuDNN 张量描述符支持的最大阶数，同 `CUDNN_DIM_MAX`。
+pub const CUDNN_DIM_MAX: usize = 8;
+
+/// `cudnnSetTensorNdDescriptor` 所需的参数。
+///
+/// 步长以元素为单位。布局的偏移不在描述符中，需要加到数据指针上。
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct CudnnTensorDesc {
+    /// 阶数，对应 `nbDims`。
+    pub nb_dims: i32,
+    /// 形状，对应 `dimA`，前 `nb_dims` 项有效。
+    pub dims: [i32; CUDNN_DIM_MAX],
+    /// 步长，对应 `strideA`，前 `nb_dims` 项有效。
+    pub strides: [i32; CUDNN_DIM_MAX],
+}
+
+/// 布局无法表示为 cuDNN 张量描述符。
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum CudnnError {
+    /// 阶数超过 [`CUDNN_DIM_MAX`]。
+    TooManyDims(usize),
+    /// 某一阶长度为 0。
+    EmptyAxis(usize),
+    /// 某一阶的步长不是正数。
+    NonPositiveStride {
+        /// 阶号。
+        axis: usize,
+        /// 以字节为单位的步长。
+        stride: isize,
+    },
+    /// 某一阶的步长不是元素大小的整数倍。
+    Misaligned {
+        /// 阶号。
+        axis: usize,
+        /// 以字节为单位的步长。
+        stride: isize,
+    },
+    /// 某一阶的长度或步长超出 `i32` 的范围。
+    Overflow(usize),
+}
+
+impl<const N: usize> ArrayLayout<N> {
+    /// 导出 cuDNN 张量描述符。
+    ///
+    /// 少于 4 阶的布局在末尾补充长度为 1 的阶，以满足 cuDNN 对低维张量的要求。
+    /// 长度为 1 的阶步长不影响寻址，统一填为 1。
+    ///
+    /// ```rust
+    /// # use ndarray_layout::{ArrayLayout, CudnnError, Endian};
+    /// let layout = ArrayLayout::<4>::new_contiguous(&[2, 3, 4], Endian::BigEndian, 2);
+    /// let desc = layout.cudnn_descriptor(2).unwrap();
+    /// assert_eq!(desc.nb_dims, 4);
+    /// assert_eq!(desc.dims[..4], [2, 3, 4, 1]);
+    /// assert_eq!(desc.strides[..4], [12, 4, 1, 1]);
+    ///
+    /// assert_eq!(
+    ///     layout.flip(1).cudnn_descriptor(2),
+    ///     Err(CudnnError::NonPositiveStride { axis: 1, stride: -8 }),
+    /// );
+    /// ```
+    pub fn cudnn_descriptor(&self, element_size: usize) -> Result<CudnnTensorDesc, CudnnError> {
+        let ndim = self.ndim;
+        if ndim > CUDNN_DIM_MAX {
+            return Err(CudnnError::TooManyDims(ndim));
+        }
+        let mut ans = CudnnTensorDesc {
+            nb_dims: ndim.max(4) as _,
+            dims: [1; CUDNN_DIM_MAX],
+            strides: [1; CUDNN_DIM_MAX],
+        };
+        for (axis, (&d, &s)) in self.shape().iter().zip(self.strides()).enumerate() {
+            if d == 0 {
+                return Err(CudnnError::EmptyAxis(axis));
+            }
+            ans.dims[axis] = i32::try_from(d).map_err(|_| CudnnError::Overflow(axis))?;
+            if d == 1 {
+                continue;
+            }
+            if s <= 0 {
+                return Err(CudnnError::NonPositiveStride { axis, stride: s });
+            }
+            if !(s as usize).is_multiple_of(element_size) {
+                return Err(CudnnError::Misaligned { axis, stride: s });
+            }
+            ans.strides[axis] =
+                i32::try_from(s as usize / element_size).map_err(|_| CudnnError::Overflow(axis))?;
+        }
+        Ok(ans)
+    }
+}
@@ -0,0 +1,131 @@
+use crate::{ArrayLayout, MemcpyChunk, ShapeMismatch};
+
+/// 硬件拷贝引擎使用的三维带间距拷贝描述，与 `cudaMemcpy3D` 等接口的参数对应。
+///
+/// 一维拷贝的 `height` 和 `depth` 为 1，二维拷贝的 `depth` 为 1。所有长度和间距都以字节计。
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct CopyDescriptor {
+    /// 每行的字节数。
+    pub width: usize,
+    /// 每层的行数。
+    pub height: usize,
+    /// 层数。
+    pub depth: usize,
+    /// 源的起始偏移。
+    pub src_offset: isize,
+    /// 目标的起始偏移。
+    pub dst_offset: isize,
+    /// 源相邻两行的间距。
+    pub src_pitch: usize,
+    /// 目标相邻两行的间距。
+    pub dst_pitch: usize,
+    /// 源相邻两层的间距。
+    pub src_slice_pitch: usize,
+    /// 目标相邻两层的间距。
+    pub dst_slice_pitch: usize,
+}
+
+/// 拷贝描述导出错误。
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum DescriptorError {
+    /// 两个布局形状不同。
+    ShapeMismatch(ShapeMismatch),
+    /// 合并后剩余的外层循环超过 2 层。
+    TooManyLevels(usize),
+    /// 外层循环的间距为负数或小于行宽，或层间距不是行间距的整数倍或小于一层的跨度。
+    InvalidPitch {
+        /// 源的间距。
+        src: isize,
+        /// 目标的间距。
+        dst: isize,
+    },
+}
+
+impl<const N: usize> ArrayLayout<N> {
+    /// 将从 `src` 到 `self` 的拷贝转换为三维带间距拷贝描述。
+    ///
+    /// ```rust
+    /// # use ndarray_layout::{ArrayLayout, CopyDescriptor, DescriptorError, Endian};
+    /// let dst = ArrayLayout::<3>::new_contiguous(&[3, 4], Endian::BigEndian, 4);
+    /// let src = ArrayLayout::<3>::new(&[3, 4], &[64, 4], 128);
+    /// assert_eq!(
+    ///     dst.copy_descriptor(&src, 4),
+    ///     Ok(CopyDescriptor {
+    ///         width: 16,
+    ///         height: 3,
+    ///         depth: 1,
+    ///         src_offset: 128,
+    ///         dst_offset: 0,
+    ///         src_pitch: 64,
+    ///         dst_pitch: 16,
+    ///         src_slice_pitch: 192,
+    ///         dst_slice_pitch: 48,
+    ///     })
+    /// );
+    ///
+    /// // 层间距不是行间距的整数倍
+    /// let dst = ArrayLayout::<3>::new_contiguous(&[2, 3, 4], Endian::BigEndian, 4);
+    /// let src = ArrayLayout::<3>::new(&[2, 3, 4], &[200, 64, 4], 0);
+    /// assert_eq!(
+    ///     dst.copy_descriptor(&src, 4),
+    ///     Err(DescriptorError::InvalidPitch { src: 200, dst: 48 })
+    /// );
+    /// // 层与层重叠
+    /// let src = ArrayLayout::<3>::new(&[2, 3, 4], &[128, 64, 4], 0);
+    /// assert_eq!(
+    ///     dst.copy_descriptor(&src, 4),
+    ///     Err(DescriptorError::InvalidPitch { src: 128, dst: 48 })
+    /// );
+    /// ```
+    pub fn copy_descriptor<const M: usize>(
+        &self,
+        src: &ArrayLayout<M>,
+        element_size: usize,
+    ) -> Result<CopyDescriptor, DescriptorError> {
+        let MemcpyChunk { elements, dst, src } = self
+            .memcpy_chunk(src, element_size)
+            .map_err(DescriptorError::ShapeMismatch)?;
+        let width = elements * element_size;
+
+        let pitch = |i: usize| {
+            let (s, d) = (src.strides()[i], dst.strides()[i]);
+            if s < width as isize || d < width as isize {
+                Err(DescriptorError::InvalidPitch { src: s, dst: d })
+            } else {
+                Ok((s as usize, d as usize))
+            }
+        };
+        let (height, depth, (src_pitch, dst_pitch), slice) = match *dst.shape() {
+            [] => (1, 1, (width, width), None),
+            [h] => (h, 1, pitch(0)?, None),
+            [d, h] => {
+                let (src_pitch, dst_pitch) = pitch(1)?;
+                let (src_slice, dst_slice) = pitch(0)?;
+                // 层间距必须能表示为整数行，且层与层之间不能重叠
+                let valid =
+                    |slice: usize, pitch: usize| slice.is_multiple_of(pitch) && slice >= pitch * h;
+                if !valid(src_slice, src_pitch) || !valid(dst_slice, dst_pitch) {
+                    return Err(DescriptorError::InvalidPitch {
+                        src: src_slice as _,
+                        dst: dst_slice as _,
+                    });
+                }
+                (h, d, (src_pitch, dst_pitch), Some((src_slice, dst_slice)))
+            }
+            [..] => return Err(DescriptorError::TooManyLevels(dst.ndim())),
+        };
+        let (src_slice_pitch, dst_slice_pitch) =
+            slice.unwrap_or((src_pitch * height, dst_pitch * height));
+        Ok(CopyDescriptor {
+            width,
+            height,
+            depth,
+            src_offset: src.offset(),
+            dst_offset: dst.offset(),
+            src_pitch,
+            dst_pitch,
+            src_slice_pitch,
+            dst_slice_pitch,
+        })
+    }
+}
@@ -0,0 +1,339 @@
+use crate::{ArrayLayout, Endian};
+use std::{iter::zip, ops::Range};
+
+/// 块循环分布，每一阶以固定大小的块轮流分配给进程网格中对应阶上的进程，同 ScaLAPACK。
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct BlockCyclic {
+    shape: Vec<usize>,
+    blocks: Vec<usize>,
+    grid: Vec<usize>,
+}
+
+impl BlockCyclic {
+    /// 指定全局形状、每一阶的块大小和进程网格的形状。
+    ///
+    /// ```rust
+    /// # use ndarray_layout::BlockCyclic;
+    /// let dist = BlockCyclic::new(&[9, 10], &[2, 3], &[2, 2]);
+    /// assert_eq!(dist.local_shape(&[0, 0]), [5, 6]);
+    /// assert_eq!(dist.local_shape(&[1, 1]), [4, 4]);
+    /// assert_eq!(dist.to_local(&[5, 4]), (vec![0, 1], vec![3, 1]));
+    /// assert_eq!(dist.to_global(&[0, 1], &[3, 1]), [5, 4]);
+    /// ```
+    pub fn new(shape: &[usize], blocks: &[usize], grid: &[usize]) -> Self {
+        assert_eq!(
+            shape.len(),
+            blocks.len(),
+            "shape and blocks must have the same length"
+        );
+        assert_eq!(
+            shape.len(),
+            grid.len(),
+            "shape and grid must have the same length"
+        );
+        assert!(
+            blocks.iter().chain(grid).all(|&d| d > 0),
+            "block sizes and grid must be positive"
+        );
+        Self {
+            shape: shape.to_vec(),
+            blocks: blocks.to_vec(),
+            grid: grid.to_vec(),
+        }
+    }
+
+    /// 全局形状。
+    #[inline]
+    pub fn shape(&self) -> &[usize] {
+        &self.shape
+    }
+
+    /// 每一阶的块大小。
+    #[inline]
+    pub fn blocks(&self) -> &[usize] {
+        &self.blocks
+    }
+
+    /// 进程网格的形状。
+    #[inline]
+    pub fn grid(&self) -> &[usize] {
+        &self.grid
+    }
+
+    /// 网格坐标为 `coords` 的进程持有的局部形状，同 ScaLAPACK 的 `NUMROC`。
+    pub fn local_shape(&self, coords: &[usize]) -> Vec<usize> {
+        assert_eq!(coords.len(), self.grid.len());
+        (0..self.shape.len())
+            .map(|axis| {
+                let (n, b, p, q) = (
+                    self.shape[axis],
+                    self.blocks[axis],
+                    self.grid[axis],
+                    coords[axis],
+                );
+                assert!(q < p, "coordinate {q} out of range for axis {axis}");
+                let nblocks = n / b;
+                let base = nblocks / p * b;
+                match q.cmp(&(nblocks % p)) {
+                    std::cmp::Ordering::Less => base + b,
+                    std::cmp::Ordering::Equal => base + n % b,
+                    std::cmp::Ordering::Greater => base,
+                }
+            })
+            .collect()
+    }
+
+    /// 网格坐标为 `coords` 的进程的局部存储布局，局部数据连续存放。
+    ///
+    /// ```rust
+    /// # use ndarray_layout::{BlockCyclic, Endian};
+    /// let dist = BlockCyclic::new(&[9, 10], &[2, 3], &[2, 2]);
+    /// let layout = dist.local_layout::<2>(&[1, 0], Endian::LittleEndian, 8);
+    /// assert_eq!(layout.shape(), &[4, 6]);
+    /// assert_eq!(layout.strides(), &[8, 32]);
+    /// assert_eq!(layout.offset(), 0);
+    /// ```
+    #[inline]
+    pub fn local_layout<const N: usize>(
+        &self,
+        coords: &[usize],
+        endian: Endian,
+        element_size: usize,
+    ) -> ArrayLayout<N> {
+        ArrayLayout::new_contiguous(&self.local_shape(coords), endian, element_size)
+    }
+
+    /// 持有全局索引 `global` 的进程的网格坐标。
+    #[inline]
+    pub fn owner(&self, global: &[usize]) -> Vec<usize> {
+        self.to_local(global).0
+    }
+
+    /// 将全局索引映射到持有它的进程的网格坐标和该进程上的局部索引。
+    pub fn to_local(&self, global: &[usize]) -> (Vec<usize>, Vec<usize>) {
+        assert_eq!(global.len(), self.shape.len());
+        global
+            .iter()
+            .enumerate()
+            .map(|(axis, &i)| {
+                assert!(
+                    i < self.shape[axis],
+                    "index {i} out of range for axis {axis}"
+                );
+                let (b, p) = (self.blocks[axis], self.grid[axis]);
+                let block = i / b;
+                (block % p, block / p * b + i % b)
+            })
+            .unzip()
+    }
+
+    /// 将网格坐标为 `coords` 的进程上的局部索引映射到全局索引。
+    pub fn to_global(&self, coords: &[usize], local: &[usize]) -> Vec<usize> {
+        assert_eq!(coords.len(), self.shape.len());
+        assert_eq!(local.len(), self.shape.len());
+        (0..self.shape.len())
+            .map(|axis| {
+                let (b, p) = (self.blocks[axis], self.grid[axis]);
+                let (q, l) = (coords[axis], local[axis]);
+                let i = (l / b * p + q) * b + l % b;
+                assert!(
+                    i < self.shape[axis],
+                    "local index {l} out of range for axis {axis}"
+                );
+                i
+            })
+            .collect()
+    }
+}
+
+/// 沿若干阶将全局布局均匀划分到多个设备上的分片布局。
+///
+/// 每个切分的阶被划分为若干段，长度不能整除时前面的段各多 1。
+/// 设备按各阶段号的行优先顺序编号。
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct ShardedLayout<const N: usize = 2> {
+    layout: ArrayLayout<N>,
+    parts: Vec<(usize, usize)>,
+}
+
+impl<const N: usize> ShardedLayout<N> {
+    /// 指定全局布局和每个切分的阶及其段数，阶号必须升序排列。
+    ///
+    /// ```rust
+    /// # use ndarray_layout::{ArrayLayout, ShardedLayout};
+    /// // [seq, head, dim] 沿 head 切分到 3 个设备
+    /// let layout = ArrayLayout::<3>::new(&[5, 8, 4], &[128, 16, 4], 0);
+    /// let sharded = ShardedLayout::new(layout, &[(1, 3)]);
+    /// assert_eq!(sharded.num_devices(), 3);
+    /// assert_eq!(sharded.ranges(2), [0..5, 6..8, 0..4]);
+    ///
+    /// let shard = sharded.shard(2);
+    /// assert_eq!(shard.shape(), &[5, 2, 4]);
+    /// assert_eq!(shard.strides(), &[128, 16, 4]);
+    /// assert_eq!(shard.offset(), 96);
+    ///
+    /// let local = sharded.local_layout(2, 4);
+    /// assert_eq!(local.shape(), &[5, 2, 4]);
+    /// assert_eq!(local.strides(), &[32, 16, 4]);
+    /// assert_eq!(local.offset(), 0);
+    ///
+    /// assert_eq!(sharded.owner(&[1, 5, 3]), (1, vec![1, 2, 3]));
+    /// ```
+    pub fn new(layout: ArrayLayout<N>, parts: &[(usize, usize)]) -> Self {
+        assert!(
+            parts.windows(2).all(|w| w[0].0 < w[1].0),
+            "sharded axes must be in ascending order"
+        );
+        assert!(
+            parts.iter().all(|&(axis, k)| axis < layout.ndim() && k > 0),
+            "invalid sharding"
+        );
+        Self {
+            layout,
+            parts: parts.to_vec(),
+        }
+    }
+
+    /// 全局布局。
+    #[inline]
+    pub fn layout(&self) -> &ArrayLayout<N> {
+        &self.layout
+    }
+
+    /// 切分的阶及其段数。
+    #[inline]
+    pub fn parts(&self) -> &[(usize, usize)] {
+        &self.parts
+    }
+
+    /// 设备的数量。
+    #[inline]
+    pub fn num_devices(&self) -> usize {
+        self.parts.iter().map(|&(_, k)| k).product()
+    }
+
+    /// 设备在每个切分的阶上的段号。
+    pub fn device_coords(&self, device: usize) -> Vec<usize> {
+        assert!(device < self.num_devices(), "device {device} out of range");
+        let mut rem = device;
+        let mut ans = vec![0; self.parts.len()];
+        for (i, &(_, k)) in self.parts.iter().enumerate().rev() {
+            ans[i] = rem % k;
+            rem /= k;
+        }
+        ans
+    }
+
+    /// 设备持有的全局索引范围。
+    pub fn ranges(&self, device: usize) -> Vec<Range<usize>> {
+        let mut ans = self
+            .layout
+            .shape()
+            .iter()
+            .map(|&d| 0..d)
+            .collect::<Vec<_>>();
+        for (&(axis, k), j) in self.parts.iter().zip(self.device_coords(device)) {
+            let d = ans[axis].end;
+            let (q, r) = (d / k, d % k);
+            let start = j * q + j.min(r);
+            ans[axis] = start..start + q + usize::from(j < r);
+        }
+        ans
+    }
+
+    /// 设备持有的部分在全局布局中的视图。
+    ///
+    /// 段数多于该阶长度时，靠后的设备不持有数据，视图在该阶上长度为 0，偏移不变。
+    pub fn shard(&self, device: usize) -> ArrayLayout<N> {
+        let mut ans = self.layout.clone();
+        let mut offset = ans.offset();
+        let mut content = ans.content_mut();
+        for (axis, (range, &s)) in zip(self.ranges(device), self.layout.strides()).enumerate() {
+            if !range.is_empty() {
+                offset += range.start as isize * s;
+            }
+            content.set_shape(axis, range.len());
+        }
+        content.set_offset(offset);
+        ans
+    }
+
+    /// 设备持有的部分在设备本地连续存储时的布局，各阶的步长顺序与全局布局相同。
+    pub fn local_layout(&self, device: usize, element_size: usize) -> ArrayLayout<N> {
+        let shape = self
+            .ranges(device)
+            .into_iter()
+            .map(|r| r.len())
+            .collect::<Vec<_>>();
+        let mut strides = vec![0; shape.len()];
+        let mut mul = element_size as isize;
+        for i in self.layout.stride_order().into_iter().rev() {
+            strides[i] = mul;
+            mul *= shape[i] as isize;
+        }
+        ArrayLayout::new(&shape, &strides, 0)
+    }
+
+    /// 持有全局索引 `global` 的设备及其在设备上的局部索引。
+    pub fn owner(&self, global: &[usize]) -> (usize, Vec<usize>) {
+        let shape = self.layout.shape();
+        assert_eq!(global.len(), shape.len());
+        let mut local = global.to_vec();
+        let mut device = 0;
+        for &(axis, k) in &self.parts {
+            let (d, i) = (shape[axis], global[axis]);
+            assert!(i < d, "index {i} out of range for axis {axis}");
+            let (q, r) = (d / k, d % k);
+            let boundary = r * (q + 1);
+            let (j, l) = if i < boundary {
+                (i / (q + 1), i % (q + 1))
+            } else {
+                (r + (i - boundary) / q, (i - boundary) % q)
+            };
+            device = device * k + j;
+            local[axis] = l;
+        }
+        (device, local)
+    }
+}
+
+#[test]
+fn test() {
+    let dist = BlockCyclic::new(&[7, 13], &[3, 2], &[2, 3]);
+    let mut counts = vec![vec![0; 13]; 7];
+    for p in 0..2 {
+        for q in 0..3 {
+            let local = dist.local_shape(&[p, q]);
+            for i in 0..local[0] {
+                for j in 0..local[1] {
+                    let global = dist.to_global(&[p, q], &[i, j]);
+                    assert_eq!(dist.to_local(&global), (vec![p, q], vec![i, j]));
+                    counts[global[0]][global[1]] += 1;
+                }
+            }
+        }
+    }
+    assert!(counts.iter().flatten().all(|&c| c == 1));
+
+    let layout = ArrayLayout::<3>::new_contiguous(&[5, 7], Endian::BigEndian, 4);
+    let sharded = ShardedLayout::new(layout, &[(0, 2), (1, 3)]);
+    for i in 0..5 {
+        for j in 0..7 {
+            let (device, local) = sharded.owner(&[i, j]);
+            let ranges = sharded.ranges(device);
+            assert_eq!(local, [i - ranges[0].start, j - ranges[1].start]);
+        }
+    }
+
+    // 段数多于该阶长度
+    let layout = ArrayLayout::<2>::new(&[2, 4], &[16, 4], 0);
+    let sharded = ShardedLayout::new(layout, &[(0, 4)]);
+    assert_eq!(sharded.shard(1), ArrayLayout::new(&[1, 4], &[16, 4], 16));
+    for device in 2..4 {
+        let shard = sharded.shard(device);
+        assert_eq!(shard.shape(), &[0, 4]);
+        assert_eq!(shard.offset(), 0);
+        assert_eq!(sharded.local_layout(device, 4).shape(), &[0, 4]);
+    }
+    assert_eq!(sharded.owner(&[1, 3]), (1, vec![0, 3]));
+}
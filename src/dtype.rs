@@ -0,0 +1,88 @@
+use crate::{ArrayLayout, Endian};
+
+/// 常用的元素数据类型。
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum DType {
+    /// 布尔值，占 1 字节。
+    Bool,
+    /// 8 位有符号整数。
+    I8,
+    /// 16 位有符号整数。
+    I16,
+    /// 32 位有符号整数。
+    I32,
+    /// 64 位有符号整数。
+    I64,
+    /// 8 位无符号整数。
+    U8,
+    /// 16 位无符号整数。
+    U16,
+    /// 32 位无符号整数。
+    U32,
+    /// 64 位无符号整数。
+    U64,
+    /// IEEE 754 半精度浮点数。
+    F16,
+    /// bfloat16 浮点数。
+    BF16,
+    /// IEEE 754 单精度浮点数。
+    F32,
+    /// IEEE 754 双精度浮点数。
+    F64,
+}
+
+impl DType {
+    /// 元素的字节数。
+    ///
+    /// ```rust
+    /// # use ndarray_layout::DType;
+    /// assert_eq!(DType::BF16.size_of(), 2);
+    /// assert_eq!(DType::F64.size_of(), 8);
+    /// ```
+    pub const fn size_of(self) -> usize {
+        match self {
+            Self::Bool | Self::I8 | Self::U8 => 1,
+            Self::I16 | Self::U16 | Self::F16 | Self::BF16 => 2,
+            Self::I32 | Self::U32 | Self::F32 => 4,
+            Self::I64 | Self::U64 | Self::F64 => 8,
+        }
+    }
+
+    /// 元素的对齐字节数。
+    #[inline]
+    pub const fn align_of(self) -> usize {
+        self.size_of()
+    }
+
+    /// 判断是否浮点类型。
+    #[inline]
+    pub const fn is_float(self) -> bool {
+        matches!(self, Self::F16 | Self::BF16 | Self::F32 | Self::F64)
+    }
+
+    /// 判断是否有符号整数类型。
+    #[inline]
+    pub const fn is_signed_int(self) -> bool {
+        matches!(self, Self::I8 | Self::I16 | Self::I32 | Self::I64)
+    }
+
+    /// 判断是否无符号整数类型。
+    #[inline]
+    pub const fn is_unsigned_int(self) -> bool {
+        matches!(self, Self::U8 | Self::U16 | Self::U32 | Self::U64)
+    }
+}
+
+impl<const N: usize> ArrayLayout<N> {
+    /// 以数据类型确定元素大小，创建紧密排布的布局。
+    ///
+    /// ```rust
+    /// # use ndarray_layout::{ArrayLayout, DType, Endian};
+    /// let layout = ArrayLayout::<3>::new_contiguous_typed(&[2, 3], Endian::BigEndian, DType::F16);
+    /// assert_eq!(layout.strides(), &[6, 2]);
+    /// ```
+    #[inline]
+    pub fn new_contiguous_typed(shape: &[usize], endian: Endian, dtype: DType) -> Self {
+        Self::new_contiguous(shape, endian, dtype.size_of())
+    }
+}
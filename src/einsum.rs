@@ -0,0 +1,211 @@
+use crate::ArrayLayout;
+
+/// 爱因斯坦求和约定的布局推导结果。
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Einsum<const N: usize> {
+    /// 所有下标，输出下标在前，按输出中的顺序排列；缩并下标在后，按首次出现的顺序排列。
+    pub labels: Vec<char>,
+    /// 每个下标的长度。
+    pub sizes: Vec<usize>,
+    /// 输出的阶数，即 `labels` 中输出下标的数量。
+    pub output_ndim: usize,
+    /// 每个操作数在全部下标上的视图，缺少的下标步长为 0，重复的下标取对角线。
+    pub operands: Vec<ArrayLayout<N>>,
+}
+
+/// 爱因斯坦求和约定解析错误。
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum EinsumError {
+    /// 无法解析的表达式。
+    Syntax(String),
+    /// 操作数数量与表达式不符。
+    OperandCount {
+        /// 表达式中的操作数数量。
+        expected: usize,
+        /// 实际的操作数数量。
+        actual: usize,
+    },
+    /// 操作数的阶数与下标数量不符。
+    RankMismatch {
+        /// 操作数的序号。
+        operand: usize,
+        /// 下标数量。
+        expected: usize,
+        /// 操作数的阶数。
+        actual: usize,
+    },
+    /// 同一下标在不同位置的长度不同且都不为 1。
+    SizeMismatch {
+        /// 下标。
+        label: char,
+        /// 不一致的两个长度。
+        sizes: [usize; 2],
+    },
+    /// 输出中的下标未在输入中出现或重复出现。
+    InvalidOutput(char),
+}
+
+impl<const N: usize> Einsum<N> {
+    /// 解析形如 `"bij,bjk->bik"` 的表达式，检查各操作数的形状是否一致，并推导输出形状和对齐的操作数视图。
+    /// 省略 `->` 时，输出由只出现一次的下标按字母顺序组成。长度为 1 的阶可以广播。
+    ///
+    /// ```rust
+    /// # use ndarray_layout::{ArrayLayout, Einsum, Endian};
+    /// let a = ArrayLayout::<4>::new_contiguous(&[8, 2, 3], Endian::BigEndian, 4);
+    /// let b = ArrayLayout::<4>::new_contiguous(&[8, 3, 5], Endian::BigEndian, 4);
+    /// let einsum = Einsum::new("bij,bjk->bik", &[&a, &b]).unwrap();
+    /// assert_eq!(einsum.labels, ['b', 'i', 'k', 'j']);
+    /// assert_eq!(einsum.output_shape(), [8, 2, 5]);
+    /// assert_eq!(einsum.operands[0].shape(), &[8, 2, 5, 3]);
+    /// assert_eq!(einsum.operands[0].strides(), &[24, 12, 0, 4]);
+    /// assert_eq!(einsum.operands[1].strides(), &[60, 0, 4, 20]);
+    /// ```
+    pub fn new(equation: &str, operands: &[&ArrayLayout<N>]) -> Result<Self, EinsumError> {
+        let equation = equation
+            .chars()
+            .filter(|c| !c.is_whitespace())
+            .collect::<String>();
+        let (inputs, output) = match equation.split_once("->") {
+            Some((inputs, output)) => (inputs, Some(output)),
+            None => (&*equation, None),
+        };
+        // `->` 只能作为唯一的分隔符出现，输入中只能有下标和逗号，输出中只能有下标
+        if let Some(c) = inputs
+            .chars()
+            .find(|&c| !c.is_ascii_alphabetic() && c != ',')
+            .or_else(|| output?.chars().find(|c| !c.is_ascii_alphabetic()))
+        {
+            return Err(EinsumError::Syntax(format!("unexpected character {c:?}")));
+        }
+        let inputs = inputs.split(',').collect::<Vec<_>>();
+        if inputs.len() != operands.len() {
+            return Err(EinsumError::OperandCount {
+                expected: inputs.len(),
+                actual: operands.len(),
+            });
+        }
+
+        // 收集下标及其长度
+        let mut seen = Vec::<(char, usize, usize)>::new(); // (下标, 长度, 出现次数)
+        for (operand, (labels, layout)) in inputs.iter().zip(operands).enumerate() {
+            let shape = layout.shape();
+            if labels.len() != shape.len() {
+                return Err(EinsumError::RankMismatch {
+                    operand,
+                    expected: labels.len(),
+                    actual: shape.len(),
+                });
+            }
+            for (label, &d) in labels.chars().zip(shape) {
+                match seen.iter_mut().find(|(c, ..)| *c == label) {
+                    Some((_, size, count)) => {
+                        *count += 1;
+                        match (*size, d) {
+                            (a, b) if a == b => {}
+                            (1, b) => *size = b,
+                            (_, 1) => {}
+                            (a, b) => {
+                                return Err(EinsumError::SizeMismatch {
+                                    label,
+                                    sizes: [a, b],
+                                })
+                            }
+                        }
+                    }
+                    None => seen.push((label, d, 1)),
+                }
+            }
+        }
+
+        let output = match output {
+            Some(output) => output.chars().collect::<Vec<_>>(),
+            None => {
+                let mut ans = seen
+                    .iter()
+                    .filter(|(.., count)| *count == 1)
+                    .map(|&(c, ..)| c)
+                    .collect::<Vec<_>>();
+                ans.sort_unstable();
+                ans
+            }
+        };
+        for (i, &c) in output.iter().enumerate() {
+            if !seen.iter().any(|&(s, ..)| s == c) || output[..i].contains(&c) {
+                return Err(EinsumError::InvalidOutput(c));
+            }
+        }
+
+        let mut labels = output.clone();
+        labels.extend(
+            seen.iter()
+                .map(|&(c, ..)| c)
+                .filter(|c| !output.contains(c)),
+        );
+        let sizes = labels
+            .iter()
+            .map(|&c| seen.iter().find(|&&(s, ..)| s == c).unwrap().1)
+            .collect::<Vec<_>>();
+
+        let operands = inputs
+            .iter()
+            .zip(operands)
+            .map(|(input, layout)| {
+                let mut strides = vec![0; labels.len()];
+                for (c, (&d, &s)) in input
+                    .chars()
+                    .zip(layout.shape().iter().zip(layout.strides()))
+                {
+                    if d != 1 {
+                        strides[labels.iter().position(|&l| l == c).unwrap()] += s;
+                    }
+                }
+                ArrayLayout::new(&sizes, &strides, layout.offset())
+            })
+            .collect();
+
+        Ok(Self {
+            output_ndim: output.len(),
+            labels,
+            sizes,
+            operands,
+        })
+    }
+
+    /// 输出的形状。
+    #[inline]
+    pub fn output_shape(&self) -> &[usize] {
+        &self.sizes[..self.output_ndim]
+    }
+}
+
+#[test]
+fn test() {
+    let a = ArrayLayout::<4>::new(&[3, 3], &[3, 1], 0);
+    let einsum = Einsum::new("ii", &[&a]).unwrap();
+    assert_eq!(einsum.output_shape(), &[] as &[usize]);
+    assert_eq!(einsum.operands[0].shape(), &[3]);
+    assert_eq!(einsum.operands[0].strides(), &[4]);
+
+    let b = ArrayLayout::<4>::new(&[3, 4], &[4, 1], 0);
+    assert!(matches!(
+        Einsum::new("ij,jk", &[&a, &b]),
+        Ok(Einsum { output_ndim: 2, .. })
+    ));
+    assert_eq!(
+        Einsum::new("ij,ij", &[&a, &b]).err(),
+        Some(EinsumError::SizeMismatch {
+            label: 'j',
+            sizes: [3, 4]
+        })
+    );
+    assert_eq!(
+        Einsum::new("ij->k", &[&a]).err(),
+        Some(EinsumError::InvalidOutput('k'))
+    );
+    for equation in ["i-j->ij", "ij->i>j", "ij->i->j", "ij>i", "ij-i", "ij->i,j"] {
+        assert!(matches!(
+            Einsum::new(equation, &[&a]),
+            Err(EinsumError::Syntax(_))
+        ));
+    }
+}
@@ -0,0 +1,337 @@
+use crate::{ArrayLayout, IndexArg, MergeError, SliceArg, TileArg};
+use std::{error::Error, fmt};
+
+/// 布局构造或变换的参数错误。
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum LayoutError {
+    /// 形状和步长的长度不同。
+    LengthMismatch {
+        /// 形状的长度。
+        shape: usize,
+        /// 步长的长度。
+        strides: usize,
+    },
+    /// 阶号超出张量的阶数。
+    AxisOutOfRange {
+        /// 阶号。
+        axis: usize,
+        /// 张量的阶数。
+        ndim: usize,
+    },
+    /// 参数中的阶没有严格升序排列。
+    UnorderedAxes {
+        /// 前一个参数的阶。
+        prev: usize,
+        /// 当前参数的阶。
+        axis: usize,
+    },
+    /// 阶号重复出现。
+    DuplicateAxis {
+        /// 重复的阶。
+        axis: usize,
+    },
+    /// 索引超出该阶的长度。
+    IndexOutOfRange {
+        /// 阶号。
+        axis: usize,
+        /// 索引。
+        index: usize,
+        /// 该阶的长度。
+        dim: usize,
+    },
+    /// 切片的起始位置超出该阶的长度。
+    SliceStartOutOfRange {
+        /// 阶号。
+        axis: usize,
+        /// 起始位置。
+        start: usize,
+        /// 该阶的长度。
+        dim: usize,
+    },
+    /// 分块的乘积不等于该阶的长度。
+    TileProductMismatch {
+        /// 阶号。
+        axis: usize,
+        /// 分块的乘积。
+        product: usize,
+        /// 该阶的长度。
+        dim: usize,
+    },
+    /// 分块的乘积溢出 `usize`。
+    TileProductOverflow {
+        /// 阶号。
+        axis: usize,
+    },
+    /// 计算紧密排布的步长时溢出。
+    StrideOverflow {
+        /// 步长溢出的阶。
+        axis: usize,
+    },
+    /// 广播的阶长度不为 1 且步长不为 0。
+    InvalidBroadcast {
+        /// 阶号。
+        axis: usize,
+        /// 该阶的长度。
+        dim: usize,
+        /// 该阶的步长。
+        stride: isize,
+    },
+    /// 步长不是元素大小的整数倍。
+    MisalignedStride {
+        /// 阶号。
+        axis: usize,
+        /// 该阶的步长。
+        stride: isize,
+        /// 元素的字节数。
+        element_size: usize,
+    },
+    /// 偏移不是元素大小的整数倍。
+    MisalignedOffset {
+        /// 偏移。
+        offset: isize,
+        /// 元素的字节数。
+        element_size: usize,
+    },
+    /// 阶数超出内联存储的容量。
+    InlineCapacityExceeded {
+        /// 阶数。
+        ndim: usize,
+        /// 内联存储的容量。
+        capacity: usize,
+    },
+    /// 无法在不拷贝数据的情况下合并。
+    Merge(MergeError),
+}
+
+impl From<MergeError> for LayoutError {
+    #[inline]
+    fn from(e: MergeError) -> Self {
+        Self::Merge(e)
+    }
+}
+
+impl fmt::Display for LayoutError {
+    /// ```rust
+    /// # use ndarray_layout::LayoutError;
+    /// let e = LayoutError::SliceStartOutOfRange { axis: 1, start: 3, dim: 3 };
+    /// assert_eq!(e.to_string(), "slice start 3 out of range for axis 1: expected start < 3");
+    /// ```
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Self::LengthMismatch { shape, strides } => write!(
+                f,
+                "shape has {shape} dims but strides has {strides}: expected equal lengths"
+            ),
+            Self::AxisOutOfRange { axis, ndim } => {
+                write!(f, "axis {axis} out of range: expected axis < {ndim}")
+            }
+            Self::UnorderedAxes { prev, axis } => write!(
+                f,
+                "axis {axis} follows axis {prev}: expected strictly ascending axes"
+            ),
+            Self::DuplicateAxis { axis } => {
+                write!(
+                    f,
+                    "axis {axis} appears more than once: expected unique axes"
+                )
+            }
+            Self::IndexOutOfRange { axis, index, dim } => write!(
+                f,
+                "index {index} out of range for axis {axis}: expected index < {dim}"
+            ),
+            Self::SliceStartOutOfRange { axis, start, dim } => write!(
+                f,
+                "slice start {start} out of range for axis {axis}: expected start < {dim}"
+            ),
+            Self::TileProductMismatch { axis, product, dim } => write!(
+                f,
+                "tiles of axis {axis} multiply to {product}: expected {dim}"
+            ),
+            Self::TileProductOverflow { axis } => {
+                write!(f, "tiles of axis {axis} multiply beyond usize")
+            }
+            Self::StrideOverflow { axis } => {
+                write!(f, "stride of axis {axis} overflows isize")
+            }
+            Self::InvalidBroadcast { axis, dim, stride } => write!(
+                f,
+                "cannot broadcast axis {axis} with dim {dim} and stride {stride}: expected dim 1 or stride 0"
+            ),
+            Self::MisalignedStride {
+                axis,
+                stride,
+                element_size,
+            } => write!(
+                f,
+                "stride {stride} of axis {axis} is not a multiple of element size {element_size}"
+            ),
+            Self::MisalignedOffset {
+                offset,
+                element_size,
+            } => write!(
+                f,
+                "offset {offset} is not a multiple of element size {element_size}"
+            ),
+            Self::InlineCapacityExceeded { ndim, capacity } => write!(
+                f,
+                "{ndim} dims exceed inline capacity: expected ndim <= {capacity}"
+            ),
+            Self::Merge(ref e) => e.fmt(f),
+        }
+    }
+}
+
+impl Error for LayoutError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Merge(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl<const N: usize> ArrayLayout<N> {
+    /// 形状和步长长度不同时返回错误的 [`new`](Self::new)。
+    ///
+    /// ```rust
+    /// # use ndarray_layout::{ArrayLayout, LayoutError};
+    /// assert!(ArrayLayout::<2>::try_new(&[2, 3], &[3, 1], 0).is_ok());
+    /// assert_eq!(
+    ///     ArrayLayout::<2>::try_new(&[2, 3], &[1], 0).err(),
+    ///     Some(LayoutError::LengthMismatch { shape: 2, strides: 1 })
+    /// );
+    /// ```
+    pub fn try_new(shape: &[usize], strides: &[isize], offset: isize) -> Result<Self, LayoutError> {
+        if shape.len() != strides.len() {
+            return Err(LayoutError::LengthMismatch {
+                shape: shape.len(),
+                strides: strides.len(),
+            });
+        }
+        Ok(Self::new(shape, strides, offset))
+    }
+
+    /// 参数无效时返回错误的 [`slice_many`](Self::slice_many)。
+    ///
+    /// ```rust
+    /// # use ndarray_layout::{ArrayLayout, LayoutError, SliceArg};
+    /// let layout = ArrayLayout::<3>::new(&[2, 3, 4], &[12, 4, 1], 0);
+    /// let arg = SliceArg { axis: 1, start: 3, step: 1, len: 1 };
+    /// assert_eq!(
+    ///     layout.try_slice_many(&[arg]).err(),
+    ///     Some(LayoutError::SliceStartOutOfRange { axis: 1, start: 3, dim: 3 })
+    /// );
+    /// ```
+    pub fn try_slice_many(&self, args: &[SliceArg]) -> Result<Self, LayoutError> {
+        self.check_axes(args.iter().map(|arg| arg.axis))?;
+        let shape = self.shape();
+        for &SliceArg {
+            axis, start, step, ..
+        } in args
+        {
+            let dim = shape[axis];
+            // 负步长的起始位置会被截断到最后一个元素，只要求该阶非空
+            if (step >= 0 && start >= dim) || dim == 0 {
+                return Err(LayoutError::SliceStartOutOfRange { axis, start, dim });
+            }
+        }
+        Ok(self.slice_many(args))
+    }
+
+    /// 参数无效时返回错误的 [`index_many`](Self::index_many)。
+    ///
+    /// ```rust
+    /// # use ndarray_layout::{ArrayLayout, IndexArg, LayoutError};
+    /// let layout = ArrayLayout::<3>::new(&[2, 3, 4], &[12, 4, 1], 0);
+    /// let args = [IndexArg { axis: 2, index: 0 }, IndexArg { axis: 1, index: 0 }];
+    /// assert_eq!(
+    ///     layout.try_index_many(&args).err(),
+    ///     Some(LayoutError::UnorderedAxes { prev: 2, axis: 1 })
+    /// );
+    /// ```
+    pub fn try_index_many(&self, args: &[IndexArg]) -> Result<Self, LayoutError> {
+        self.check_axes(args.iter().map(|arg| arg.axis))?;
+        let shape = self.shape();
+        for &IndexArg { axis, index } in args {
+            let dim = shape[axis];
+            if index >= dim {
+                return Err(LayoutError::IndexOutOfRange { axis, index, dim });
+            }
+        }
+        Ok(self.index_many(args))
+    }
+
+    /// 参数无效时返回错误的 [`tile_many`](Self::tile_many)。
+    ///
+    /// ```rust
+    /// # use ndarray_layout::{ArrayLayout, Endian, LayoutError, TileArg};
+    /// let layout = ArrayLayout::<3>::new(&[2, 6], &[6, 1], 0);
+    /// let arg = TileArg { axis: 1, endian: Endian::BigEndian, tiles: &[4, 2] };
+    /// assert_eq!(
+    ///     layout.try_tile_many(&[arg]).err(),
+    ///     Some(LayoutError::TileProductMismatch { axis: 1, product: 8, dim: 6 })
+    /// );
+    ///
+    /// let arg = TileArg { axis: 1, endian: Endian::BigEndian, tiles: &[usize::MAX, 3] };
+    /// assert_eq!(
+    ///     layout.try_tile_many(&[arg]).err(),
+    ///     Some(LayoutError::TileProductOverflow { axis: 1 })
+    /// );
+    /// ```
+    pub fn try_tile_many(&self, args: &[TileArg]) -> Result<Self, LayoutError> {
+        self.check_axes(args.iter().map(|arg| arg.axis))?;
+        let shape = self.shape();
+        for &TileArg { axis, tiles, .. } in args {
+            let dim = shape[axis];
+            let product = tiles
+                .iter()
+                .try_fold(1usize, |acc, &t| acc.checked_mul(t))
+                .ok_or(LayoutError::TileProductOverflow { axis })?;
+            if product != dim {
+                return Err(LayoutError::TileProductMismatch { axis, product, dim });
+            }
+        }
+        Ok(self.tile_many(args))
+    }
+
+    /// 参数无效时返回错误的 [`transpose`](Self::transpose)。
+    ///
+    /// ```rust
+    /// # use ndarray_layout::{ArrayLayout, LayoutError};
+    /// let layout = ArrayLayout::<3>::new(&[2, 3, 4], &[12, 4, 1], 0);
+    /// assert!(layout.try_transpose(&[2, 0]).unwrap() == layout.transpose(&[2, 0]));
+    /// assert_eq!(
+    ///     layout.try_transpose(&[1, 1]).err(),
+    ///     Some(LayoutError::DuplicateAxis { axis: 1 })
+    /// );
+    /// ```
+    pub fn try_transpose(&self, perm: &[usize]) -> Result<Self, LayoutError> {
+        let ndim = self.ndim;
+        let mut seen = vec![false; ndim];
+        for &axis in perm {
+            if axis >= ndim {
+                return Err(LayoutError::AxisOutOfRange { axis, ndim });
+            }
+            if std::mem::replace(&mut seen[axis], true) {
+                return Err(LayoutError::DuplicateAxis { axis });
+            }
+        }
+        Ok(self.transpose(perm))
+    }
+
+    /// 检查参数的阶都在范围内且严格升序排列。
+    fn check_axes(&self, axes: impl IntoIterator<Item = usize>) -> Result<(), LayoutError> {
+        let ndim = self.ndim;
+        let mut prev = None;
+        for axis in axes {
+            if axis >= ndim {
+                return Err(LayoutError::AxisOutOfRange { axis, ndim });
+            }
+            if let Some(prev) = prev.filter(|&prev| prev >= axis) {
+                return Err(LayoutError::UnorderedAxes { prev, axis });
+            }
+            prev = Some(axis);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,67 @@
+use crate::ArrayLayout;
+use std::{marker::PhantomData, slice::from_raw_parts};
+
+/// 可以直接传递给 C/CUDA 代码的布局描述符。
+///
+/// 形状和步长以指针的形式借用自 [`ArrayLayout`]，描述符的生命周期不能超过被借用的布局。
+/// 在 C 中对应的定义为：
+///
+/// ```c
+/// struct FfiLayout {
+///     size_t ndim;
+///     ptrdiff_t offset;
+///     const size_t *shape;
+///     const ptrdiff_t *strides;
+/// };
+/// ```
+#[repr(C)]
+#[derive(Clone, Copy, Debug)]
+pub struct FfiLayout<'a> {
+    /// 阶数。
+    pub ndim: usize,
+    /// 以字节为单位的偏移。
+    pub offset: isize,
+    /// 指向 `ndim` 个形状的指针。
+    pub shape: *const usize,
+    /// 指向 `ndim` 个以字节为单位的步长的指针。
+    pub strides: *const isize,
+    _phantom: PhantomData<&'a ()>,
+}
+
+impl<const N: usize> ArrayLayout<N> {
+    /// 借用布局的元信息生成 FFI 描述符。
+    ///
+    /// ```rust
+    /// # use ndarray_layout::ArrayLayout;
+    /// let layout = ArrayLayout::<2>::new(&[2, 3, 4], &[12, -4, 1], 8);
+    /// let ffi = layout.to_ffi();
+    /// assert_eq!(ffi.ndim, 3);
+    /// assert_eq!(ffi.offset, 8);
+    /// assert!(unsafe { ArrayLayout::<2>::from_ffi(&ffi) } == layout);
+    /// ```
+    #[inline]
+    pub fn to_ffi(&self) -> FfiLayout<'_> {
+        let content = self.content();
+        FfiLayout {
+            ndim: self.ndim,
+            offset: content.offset(),
+            shape: content.shape().as_ptr(),
+            strides: content.strides().as_ptr(),
+            _phantom: PhantomData,
+        }
+    }
+
+    /// 从 FFI 描述符复制元信息构造布局。
+    ///
+    /// # Safety
+    ///
+    /// `ffi.shape` 和 `ffi.strides` 必须分别指向 `ffi.ndim` 个有效的元素，`ndim` 为 0 时可以为空指针。
+    pub unsafe fn from_ffi(ffi: &FfiLayout) -> Self {
+        if ffi.ndim == 0 {
+            return Self::new(&[], &[], ffi.offset);
+        }
+        let shape = unsafe { from_raw_parts(ffi.shape, ffi.ndim) };
+        let strides = unsafe { from_raw_parts(ffi.strides, ffi.ndim) };
+        Self::new(shape, strides, ffi.offset)
+    }
+}
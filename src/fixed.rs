@@ -0,0 +1,95 @@
+use crate::{ArrayLayout, Endian};
+
+/// 阶数和形状在编译期确定的布局。
+///
+/// 所有方法都是 `const fn`，适用于 3×3 卷积核等形状固定的场景。
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct StaticLayout<const D: usize> {
+    /// 形状。
+    pub shape: [usize; D],
+    /// 步长。
+    pub strides: [isize; D],
+    /// 偏移。
+    pub offset: isize,
+}
+
+impl<const D: usize> StaticLayout<D> {
+    /// 以形状、步长和偏移构造布局。
+    #[inline]
+    pub const fn new(shape: [usize; D], strides: [isize; D], offset: isize) -> Self {
+        Self {
+            shape,
+            strides,
+            offset,
+        }
+    }
+
+    /// 紧密排布的布局，同 [`ArrayLayout::new_contiguous`]。
+    ///
+    /// ```rust
+    /// # use ndarray_layout::{ArrayLayout, Endian, StaticLayout};
+    /// const KERNEL: StaticLayout<4> = StaticLayout::new_contiguous([8, 4, 3, 3], Endian::BigEndian, 4);
+    /// const _: () = assert!(KERNEL.num_elements() == 288);
+    /// const _: () = assert!(KERNEL.offset_of([1, 2, 1, 0]) == 4 * (36 + 18 + 3));
+    ///
+    /// let layout: ArrayLayout<4> = KERNEL.into();
+    /// assert_eq!(layout, ArrayLayout::new_contiguous(&[8, 4, 3, 3], Endian::BigEndian, 4));
+    /// ```
+    pub const fn new_contiguous(shape: [usize; D], endian: Endian, element_size: usize) -> Self {
+        let mut strides = [0; D];
+        let mut mul = element_size as isize;
+        let mut i = 0;
+        while i < D {
+            let axis = match endian {
+                Endian::BigEndian => D - 1 - i,
+                Endian::LittleEndian => i,
+            };
+            strides[axis] = mul;
+            mul *= shape[axis] as isize;
+            i += 1
+        }
+        Self::new(shape, strides, 0)
+    }
+
+    /// 阶数。
+    #[inline]
+    pub const fn ndim(&self) -> usize {
+        D
+    }
+
+    /// 元素数量，即形状的乘积。
+    pub const fn num_elements(&self) -> usize {
+        let mut ans = 1;
+        let mut i = 0;
+        while i < D {
+            ans *= self.shape[i];
+            i += 1
+        }
+        ans
+    }
+
+    /// 计算多维索引对应的偏移，索引越界时 panic。
+    pub const fn offset_of(&self, indices: [usize; D]) -> isize {
+        let mut ans = self.offset;
+        let mut i = 0;
+        while i < D {
+            assert!(indices[i] < self.shape[i], "index out of range");
+            ans += indices[i] as isize * self.strides[i];
+            i += 1
+        }
+        ans
+    }
+
+    /// 转换为动态布局。
+    #[inline]
+    pub fn to_layout<const N: usize>(&self) -> ArrayLayout<N> {
+        ArrayLayout::new(&self.shape, &self.strides, self.offset)
+    }
+}
+
+impl<const D: usize, const N: usize> From<StaticLayout<D>> for ArrayLayout<N> {
+    #[inline]
+    fn from(value: StaticLayout<D>) -> Self {
+        value.to_layout()
+    }
+}
@@ -0,0 +1,85 @@
+use crate::ArrayLayout;
+
+/// 以 BLAS 列优先约定描述的矩阵，步长均以元素为单位。
+///
+/// 布局的偏移不在视图中，需要加到数据指针上。
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct GemmView {
+    /// 批量数，2 阶布局为 1。
+    pub batch: usize,
+    /// 相邻两个矩阵之间的步长，2 阶布局为 0。
+    pub batch_stride: usize,
+    /// 矩阵的行数。
+    pub rows: usize,
+    /// 矩阵的列数。
+    pub cols: usize,
+    /// 主维度。
+    pub ld: usize,
+    /// 存储是否为转置，即按行优先排布。
+    pub trans: bool,
+}
+
+impl<const N: usize> ArrayLayout<N> {
+    /// 将 2 阶或带批量的 3 阶布局表示为 BLAS 矩阵。
+    ///
+    /// 列步长为 1 时视为列优先存储，行步长为 1 时视为转置存储，两者都满足时优先视为列优先存储。
+    /// 无法表示为 BLAS 矩阵时返回 `None`。
+    ///
+    /// ```rust
+    /// # use ndarray_layout::{ArrayLayout, Endian, GemmView};
+    /// let layout = ArrayLayout::<3>::new_contiguous(&[4, 2, 3], Endian::BigEndian, 4);
+    /// assert_eq!(
+    ///     layout.as_gemm_matrix(4),
+    ///     Some(GemmView { batch: 4, batch_stride: 6, rows: 2, cols: 3, ld: 3, trans: true })
+    /// );
+    ///
+    /// let layout = ArrayLayout::<3>::new(&[2, 3], &[4, 16], 0);
+    /// assert_eq!(
+    ///     layout.as_gemm_matrix(4),
+    ///     Some(GemmView { batch: 1, batch_stride: 0, rows: 2, cols: 3, ld: 4, trans: false })
+    /// );
+    ///
+    /// let layout = ArrayLayout::<3>::new(&[2, 3], &[8, 8], 0);
+    /// assert_eq!(layout.as_gemm_matrix(4), None);
+    /// ```
+    pub fn as_gemm_matrix(&self, element_size: usize) -> Option<GemmView> {
+        let elements = |s: isize| {
+            let s = usize::try_from(s).ok()?;
+            s.is_multiple_of(element_size).then_some(s / element_size)
+        };
+
+        let shape = self.shape();
+        let strides = self.strides();
+        let (batch, batch_stride, [rows, cols], [rs, cs]) = match self.ndim {
+            2 => (1, 0, [shape[0], shape[1]], [strides[0], strides[1]]),
+            3 => {
+                let bs = if shape[0] == 1 {
+                    0
+                } else {
+                    elements(strides[0])?
+                };
+                (shape[0], bs, [shape[1], shape[2]], [strides[1], strides[2]])
+            }
+            _ => return None,
+        };
+
+        // 长度为 1 的阶的步长不影响寻址
+        let rs = if rows == 1 { None } else { Some(elements(rs)?) };
+        let cs = if cols == 1 { None } else { Some(elements(cs)?) };
+        let (trans, ld) = match (rs, cs) {
+            (Some(1) | None, Some(ld)) if ld >= rows.max(1) => (false, ld),
+            (Some(1) | None, None) => (false, rows.max(1)),
+            (Some(ld), Some(1) | None) if ld >= cols.max(1) => (true, ld),
+            _ => return None,
+        };
+
+        Some(GemmView {
+            batch,
+            batch_stride,
+            rows,
+            cols,
+            ld,
+            trans,
+        })
+    }
+}
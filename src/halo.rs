@@ -0,0 +1,115 @@
+use crate::ArrayLayout;
+use std::iter::zip;
+
+/// 一个面上的边界交换区域。
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct HaloFace<const N: usize = 2> {
+    /// 需要发送给邻居的内部边界。
+    pub send: ArrayLayout<N>,
+    /// 接收邻居数据的幽灵区域。
+    pub recv: ArrayLayout<N>,
+}
+
+/// 模板计算的幽灵区域划分。
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Halo<const N: usize = 2> {
+    /// 不含幽灵区域的内部。
+    pub interior: ArrayLayout<N>,
+    /// 每一阶低端和高端两个面上的交换区域。
+    pub faces: Vec<[HaloFace<N>; 2]>,
+}
+
+impl<const N: usize> ArrayLayout<N> {
+    /// 将包含宽度为 `widths` 的幽灵区域的局部布局划分为内部和每个面上的交换区域。
+    ///
+    /// 每个面上的区域在其他阶上只覆盖内部，不包含棱和角。
+    /// 宽度为 0 的阶上两个面都是长度为 0 的空区域。
+    ///
+    /// ```rust
+    /// # use ndarray_layout::ArrayLayout;
+    /// let layout = ArrayLayout::<2>::new(&[6, 8], &[32, 4], 0);
+    /// let halo = layout.halo(&[1, 2]);
+    /// assert_eq!(halo.interior.shape(), &[4, 4]);
+    /// assert_eq!(halo.interior.offset(), 40);
+    ///
+    /// let [low, high] = &halo.faces[1];
+    /// assert_eq!(low.recv.shape(), &[4, 2]);
+    /// assert_eq!(low.recv.offset(), 32);
+    /// assert_eq!(low.send.offset(), 40);
+    /// assert_eq!(high.send.offset(), 48);
+    /// assert_eq!(high.recv.offset(), 56);
+    /// assert_eq!(high.recv.strides(), &[32, 4]);
+    /// ```
+    pub fn halo(&self, widths: &[usize]) -> Halo<N> {
+        let shape = self.shape();
+        assert_eq!(shape.len(), widths.len(), "widths must match ndim");
+        assert!(
+            zip(shape, widths).all(|(&d, &h)| d >= 2 * h),
+            "halo is wider than the layout"
+        );
+
+        // 区域已经过检查，直接设置元信息，避免长度为 0 的区域在切片时越界
+        let window = |ranges: &[(usize, usize)]| {
+            let mut ans = self.clone();
+            let mut offset = self.offset();
+            let mut content = ans.content_mut();
+            for (axis, (&(start, len), &s)) in zip(ranges, self.strides()).enumerate() {
+                offset += start as isize * s;
+                content.set_shape(axis, len);
+            }
+            content.set_offset(offset);
+            ans
+        };
+
+        let interior_ranges = zip(shape, widths)
+            .map(|(&d, &h)| (h, d - 2 * h))
+            .collect::<Vec<_>>();
+        let interior = window(&interior_ranges);
+
+        let faces = zip(shape, widths)
+            .enumerate()
+            .map(|(axis, (&d, &h))| {
+                let face = |start| {
+                    let mut ranges = interior_ranges.clone();
+                    // 宽度为 0 的面为空，偏移与内部相同
+                    ranges[axis] = if h == 0 { (h, 0) } else { (start, h) };
+                    window(&ranges)
+                };
+                [
+                    HaloFace {
+                        send: face(h),
+                        recv: face(0),
+                    },
+                    HaloFace {
+                        send: face(d - 2 * h),
+                        recv: face(d - h),
+                    },
+                ]
+            })
+            .collect();
+
+        Halo { interior, faces }
+    }
+}
+
+#[test]
+fn test() {
+    let layout = ArrayLayout::<2>::new(&[6, 8], &[32, 4], 0);
+    let halo = layout.halo(&[1, 0]);
+    assert_eq!(halo.interior, layout.slice(0, 1, 1, 4));
+    let [low, high] = &halo.faces[0];
+    assert_eq!(low.recv.shape(), &[1, 8]);
+    assert_eq!(high.recv.offset(), 160);
+    for face in &halo.faces[1] {
+        assert_eq!(face.send.shape(), &[4, 0]);
+        assert_eq!(face.recv.shape(), &[4, 0]);
+        assert_eq!(face.recv.offset(), halo.interior.offset());
+    }
+
+    let halo = layout.halo(&[0, 0]);
+    assert_eq!(halo.interior, layout);
+    assert!(halo.faces.iter().flatten().all(|f| f.send.is_empty()));
+
+    let halo = ArrayLayout::<2>::new(&[0, 4], &[16, 4], 0).halo(&[0, 2]);
+    assert_eq!(halo.interior.shape(), &[0, 0]);
+}
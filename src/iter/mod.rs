@@ -0,0 +1,11 @@
+﻿mod offsets;
+#[cfg(feature = "rayon")]
+mod par;
+mod tiles;
+mod zip;
+
+pub use offsets::Offsets;
+#[cfg(feature = "rayon")]
+pub use par::ParLayouts;
+pub use tiles::Tiles;
+pub use zip::{ShapeMismatch, ZipOffsets};
@@ -0,0 +1,63 @@
+﻿use crate::ArrayLayout;
+
+/// 按逻辑顺序遍历张量所有元素偏移的迭代器。
+#[derive(Clone, Debug)]
+pub struct Offsets {
+    shape: Vec<usize>,
+    strides: Vec<isize>,
+    index: Vec<usize>,
+    offset: isize,
+    len: usize,
+}
+
+impl<const N: usize> ArrayLayout<N> {
+    /// 按行优先的逻辑顺序遍历张量所有元素的偏移。
+    ///
+    /// ```rust
+    /// # use ndarray_layout::ArrayLayout;
+    /// let layout = ArrayLayout::<3>::new(&[2, 3], &[1, -2], 4);
+    /// let offsets = layout.offsets().collect::<Vec<_>>();
+    /// assert_eq!(offsets, [4, 2, 0, 5, 3, 1]);
+    /// ```
+    pub fn offsets(&self) -> Offsets {
+        Offsets {
+            shape: self.shape().to_vec(),
+            strides: self.strides().to_vec(),
+            index: vec![0; self.ndim],
+            offset: self.offset(),
+            len: self.num_elements(),
+        }
+    }
+}
+
+impl Iterator for Offsets {
+    type Item = isize;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        self.len -= 1;
+
+        let ans = self.offset;
+        if self.len > 0 {
+            for axis in (0..self.shape.len()).rev() {
+                self.index[axis] += 1;
+                self.offset += self.strides[axis];
+                if self.index[axis] < self.shape[axis] {
+                    break;
+                }
+                self.index[axis] = 0;
+                self.offset -= self.strides[axis] * self.shape[axis] as isize;
+            }
+        }
+        Some(ans)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl ExactSizeIterator for Offsets {}
@@ -0,0 +1,155 @@
+﻿use crate::{ArrayLayout, Chunks, Split, SplitGrid, Tiles};
+use rayon::{
+    iter::plumbing::{bridge, Consumer, Producer, ProducerCallback, UnindexedConsumer},
+    prelude::*,
+};
+
+impl<const N: usize> ArrayLayout<N> {
+    /// 并行遍历张量所有元素的偏移，保持逻辑顺序。
+    ///
+    /// 以最外层阶划分任务，每个任务内部按 [`offsets`](Self::offsets) 的方式以步长累加遍历。
+    ///
+    /// ```rust
+    /// # use ndarray_layout::ArrayLayout;
+    /// use rayon::prelude::*;
+    ///
+    /// let layout = ArrayLayout::<3>::new(&[2, 3], &[1, -2], 4);
+    /// let offsets = layout.par_offsets().collect::<Vec<_>>();
+    /// assert_eq!(offsets, layout.offsets().collect::<Vec<_>>());
+    /// ```
+    pub fn par_offsets(&self) -> impl ParallelIterator<Item = isize> + '_ {
+        let outer = if self.ndim == 0 { 1 } else { self.shape()[0] };
+        (0..outer).into_par_iter().flat_map_iter(move |i| {
+            if self.ndim == 0 {
+                self.offsets()
+            } else {
+                self.index(0, i).offsets()
+            }
+        })
+    }
+}
+
+/// 可以在任意位置一分为二的子布局迭代器的并行版本。
+///
+/// 任务通过 [`Producer::split_at`] 直接划分序号范围，不需要先串行收集所有子布局。
+pub struct ParLayouts<P>(P);
+
+impl<P> ParallelIterator for ParLayouts<P>
+where
+    P: Producer<IntoIter = P> + ExactSizeIterator<Item = <P as Producer>::Item>,
+    <P as Producer>::Item: Send,
+{
+    type Item = <P as Producer>::Item;
+
+    #[inline]
+    fn drive_unindexed<C>(self, consumer: C) -> C::Result
+    where
+        C: UnindexedConsumer<Self::Item>,
+    {
+        bridge(self, consumer)
+    }
+
+    #[inline]
+    fn opt_len(&self) -> Option<usize> {
+        Some(ExactSizeIterator::len(&self.0))
+    }
+}
+
+impl<P> IndexedParallelIterator for ParLayouts<P>
+where
+    P: Producer<IntoIter = P> + ExactSizeIterator<Item = <P as Producer>::Item>,
+    <P as Producer>::Item: Send,
+{
+    #[inline]
+    fn len(&self) -> usize {
+        ExactSizeIterator::len(&self.0)
+    }
+
+    #[inline]
+    fn drive<C: Consumer<Self::Item>>(self, consumer: C) -> C::Result {
+        bridge(self, consumer)
+    }
+
+    #[inline]
+    fn with_producer<CB: ProducerCallback<Self::Item>>(self, callback: CB) -> CB::Output {
+        callback.callback(self.0)
+    }
+}
+
+macro_rules! into_par_iter {
+    ($ty:ident) => {
+        impl<const N: usize> Producer for $ty<'_, N> {
+            type Item = ArrayLayout<N>;
+            type IntoIter = Self;
+
+            #[inline]
+            fn into_iter(self) -> Self::IntoIter {
+                self
+            }
+
+            #[inline]
+            fn split_at(self, index: usize) -> (Self, Self) {
+                self.split_at(index)
+            }
+        }
+
+        impl<'a, const N: usize> IntoParallelIterator for $ty<'a, N> {
+            type Iter = ParLayouts<Self>;
+            type Item = ArrayLayout<N>;
+
+            #[inline]
+            fn into_par_iter(self) -> Self::Iter {
+                ParLayouts(self)
+            }
+        }
+    };
+}
+
+into_par_iter!(Split);
+into_par_iter!(Chunks);
+into_par_iter!(SplitGrid);
+into_par_iter!(Tiles);
+
+#[test]
+fn test() {
+    let layout = ArrayLayout::<3>::new(&[4, 6], &[6, 1], 0);
+    let offsets = layout
+        .chunks(1, 4)
+        .into_par_iter()
+        .map(|l| l.offset())
+        .collect::<Vec<_>>();
+    assert_eq!(offsets, [0, 4]);
+
+    let sum = layout
+        .iter_tiles(&[3, 3])
+        .into_par_iter()
+        .map(|l| l.num_elements())
+        .sum::<usize>();
+    assert_eq!(sum, 24);
+
+    let tiles = layout.iter_tiles(&[3, 4]);
+    assert_eq!(
+        tiles.clone().into_par_iter().collect::<Vec<_>>(),
+        tiles.collect::<Vec<_>>()
+    );
+
+    let grid = layout.split_grid(&[(0, &[1, 3]), (1, &[2, 0, 4])]);
+    assert_eq!(grid.clone().into_par_iter().len(), 6);
+    assert_eq!(
+        grid.clone()
+            .into_par_iter()
+            .with_min_len(1)
+            .collect::<Vec<_>>(),
+        grid.collect::<Vec<_>>()
+    );
+
+    let split = layout.split(1, &[1, 2, 3]);
+    assert_eq!(
+        split.clone().into_par_iter().rev().collect::<Vec<_>>(),
+        split.rev().collect::<Vec<_>>()
+    );
+
+    let chunks = layout.chunks(1, 4);
+    let (a, b) = chunks.clone().split_at(1);
+    assert_eq!(a.chain(b).collect::<Vec<_>>(), chunks.collect::<Vec<_>>());
+}
@@ -0,0 +1,108 @@
+﻿use crate::ArrayLayout;
+use std::iter::zip;
+
+/// 按分块遍历张量的迭代器。
+#[derive(Clone)]
+pub struct Tiles<'a, const N: usize> {
+    src: &'a ArrayLayout<N>,
+    tile: Vec<usize>,
+    start: usize,
+    end: usize,
+}
+
+impl<const N: usize> ArrayLayout<N> {
+    /// 将张量划分为形状为 `tile` 的分块，按行优先顺序依次产生每个分块的子布局，边缘的分块可能更小。
+    ///
+    /// ```rust
+    /// # use ndarray_layout::ArrayLayout;
+    /// let layout = ArrayLayout::<3>::new(&[5, 6], &[6, 1], 0);
+    /// let tiles = layout.iter_tiles(&[2, 4]).collect::<Vec<_>>();
+    /// assert_eq!(tiles.len(), 6);
+    ///
+    /// assert_eq!(tiles[1].shape(), &[2, 2]);
+    /// assert_eq!(tiles[1].offset(), 4);
+    ///
+    /// assert_eq!(tiles[4].shape(), &[1, 4]);
+    /// assert_eq!(tiles[4].strides(), &[6, 1]);
+    /// assert_eq!(tiles[4].offset(), 24);
+    /// ```
+    pub fn iter_tiles(&self, tile: &[usize]) -> Tiles<'_, N> {
+        assert_eq!(
+            tile.len(),
+            self.ndim,
+            "tile and shape must have the same length"
+        );
+        assert!(tile.iter().all(|&t| t > 0), "tile sizes must be positive");
+        let end = zip(self.shape(), tile)
+            .map(|(&d, &t)| d.div_ceil(t))
+            .product();
+        Tiles {
+            src: self,
+            tile: tile.to_vec(),
+            start: 0,
+            end,
+        }
+    }
+}
+
+impl<const N: usize> Tiles<'_, N> {
+    /// 按行优先顺序的第 `k` 个分块，直接修改元信息而不构造切片参数。
+    fn tile(&self, mut k: usize) -> ArrayLayout<N> {
+        let mut ans = self.src.clone();
+        let mut offset = ans.offset();
+        let mut content = ans.content_mut();
+        let iter = zip(self.src.shape(), zip(self.src.strides(), &self.tile)).enumerate();
+        for (axis, (&d, (&s, &t))) in iter.rev() {
+            let n = d.div_ceil(t);
+            let start = k % n * t;
+            k /= n;
+            offset += start as isize * s;
+            content.set_shape(axis, t.min(d - start));
+        }
+        content.set_offset(offset);
+        ans
+    }
+
+    /// 在第 `index` 个分块处一分为二。
+    #[cfg(feature = "rayon")]
+    pub(crate) fn split_at(self, index: usize) -> (Self, Self) {
+        let mid = self.start + index;
+        (
+            Self {
+                end: mid,
+                ..self.clone()
+            },
+            Self { start: mid, ..self },
+        )
+    }
+}
+
+impl<const N: usize> Iterator for Tiles<'_, N> {
+    type Item = ArrayLayout<N>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        (self.start < self.end).then(|| {
+            self.start += 1;
+            self.tile(self.start - 1)
+        })
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.end - self.start;
+        (len, Some(len))
+    }
+}
+
+impl<const N: usize> DoubleEndedIterator for Tiles<'_, N> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        (self.start < self.end).then(|| {
+            self.end -= 1;
+            self.tile(self.end)
+        })
+    }
+}
+
+impl<const N: usize> ExactSizeIterator for Tiles<'_, N> {}
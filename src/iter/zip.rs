@@ -0,0 +1,94 @@
+﻿use crate::ArrayLayout;
+
+/// 形状不匹配错误。
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct ShapeMismatch {
+    /// 左侧布局的形状。
+    pub left: Vec<usize>,
+    /// 右侧布局的形状。
+    pub right: Vec<usize>,
+}
+
+/// 按相同的逻辑顺序同时遍历两个张量元素偏移的迭代器。
+#[derive(Clone, Debug)]
+pub struct ZipOffsets {
+    shape: Vec<usize>,
+    strides: Vec<[isize; 2]>,
+    index: Vec<usize>,
+    offset: [isize; 2],
+    len: usize,
+}
+
+impl<const N: usize> ArrayLayout<N> {
+    /// 按行优先的逻辑顺序同时遍历两个形状相同的张量，产生对应元素的偏移对。
+    ///
+    /// ```rust
+    /// # use ndarray_layout::{ArrayLayout, Endian};
+    /// let src = ArrayLayout::<3>::new_contiguous(&[2, 3], Endian::BigEndian, 1);
+    /// let dst = ArrayLayout::<3>::new_contiguous(&[2, 3], Endian::LittleEndian, 1);
+    /// let pairs = src.zip_offsets(&dst).unwrap().collect::<Vec<_>>();
+    /// assert_eq!(pairs, [(0, 0), (1, 2), (2, 4), (3, 1), (4, 3), (5, 5)]);
+    ///
+    /// assert!(src.zip_offsets(&dst.transpose(&[1, 0])).is_err());
+    /// ```
+    pub fn zip_offsets<const M: usize>(
+        &self,
+        other: &ArrayLayout<M>,
+    ) -> Result<ZipOffsets, ShapeMismatch> {
+        let shape = self.shape();
+        if shape != other.shape() {
+            return Err(ShapeMismatch {
+                left: shape.to_vec(),
+                right: other.shape().to_vec(),
+            });
+        }
+        Ok(ZipOffsets {
+            shape: shape.to_vec(),
+            strides: self
+                .strides()
+                .iter()
+                .zip(other.strides())
+                .map(|(&a, &b)| [a, b])
+                .collect(),
+            index: vec![0; shape.len()],
+            offset: [self.offset(), other.offset()],
+            len: self.num_elements(),
+        })
+    }
+}
+
+impl Iterator for ZipOffsets {
+    type Item = (isize, isize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        self.len -= 1;
+
+        let [a, b] = self.offset;
+        if self.len > 0 {
+            for axis in (0..self.shape.len()).rev() {
+                let [sa, sb] = self.strides[axis];
+                self.index[axis] += 1;
+                self.offset[0] += sa;
+                self.offset[1] += sb;
+                if self.index[axis] < self.shape[axis] {
+                    break;
+                }
+                let d = self.shape[axis] as isize;
+                self.index[axis] = 0;
+                self.offset[0] -= sa * d;
+                self.offset[1] -= sb * d;
+            }
+        }
+        Some((a, b))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl ExactSizeIterator for ZipOffsets {}
@@ -9,7 +9,17 @@ pub struct ArrayLayout<const N: usize = 2> {
 
 union Union<const N: usize> {
     ptr: NonNull<usize>,
-    _inlined: (isize, [usize; N], [isize; N]),
+    _inlined: Inlined<N>,
+}
+
+/// Inline storage, laid out exactly like the heap allocation: offset, then shape, then strides
+/// packed by the actual ndim, so the two arrays are only a buffer of `2N` slots.
+#[derive(Clone, Copy)]
+#[repr(C)]
+struct Inlined<const N: usize> {
+    offset: isize,
+    head: [usize; N],
+    tail: [isize; N],
 }
 
 impl<const N: usize> Clone for ArrayLayout<N> {
@@ -28,10 +38,76 @@ impl<const N: usize> PartialEq for ArrayLayout<N> {
 
 impl<const N: usize> Eq for ArrayLayout<N> {}
 
+impl<const N: usize> Default for ArrayLayout<N> {
+    /// Creates a 0-dimensional (scalar) layout with offset 0.
+    ///
+    /// ```rust
+    /// # use ndarray_layout::ArrayLayout;
+    /// let scalar = ArrayLayout::<4>::default();
+    /// assert_eq!(scalar.ndim(), 0);
+    /// assert_eq!(scalar.num_elements(), 1);
+    /// assert_eq!(scalar.offsets().collect::<Vec<_>>(), [0]);
+    /// assert_eq!(scalar.data_range(), 0..=0);
+    ///
+    /// let layout = ArrayLayout::<4>::new(&[2, 3], &[3, 1], 0);
+    /// assert_eq!(layout.index(0, 1).index(0, 2), ArrayLayout::new(&[], &[], 5));
+    /// ```
+    #[inline]
+    fn default() -> Self {
+        Self::new(&[], &[], 0)
+    }
+}
+
+impl<const N: usize> Hash for ArrayLayout<N> {
+    /// Hashes the same metadata compared by `PartialEq`, regardless of inline or heap storage.
+    ///
+    /// ```rust
+    /// # use ndarray_layout::ArrayLayout;
+    /// use std::collections::HashMap;
+    ///
+    /// let mut kernels = HashMap::new();
+    /// kernels.insert(ArrayLayout::<2>::new(&[2, 3, 4], &[12, 4, 1], 0), "heap");
+    /// kernels.insert(ArrayLayout::<2>::new(&[3, 4], &[4, 1], 0), "inline");
+    /// assert_eq!(kernels[&ArrayLayout::<2>::new(&[2, 3, 4], &[12, 4, 1], 0)], "heap");
+    /// assert_eq!(kernels[&ArrayLayout::<2>::new(&[3, 4], &[4, 1], 0)], "inline");
+    /// ```
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.ndim.hash(state);
+        self.content().as_slice().hash(state);
+    }
+}
+
+impl<const N: usize> fmt::Debug for ArrayLayout<N> {
+    /// Shows ndim, shape, strides, offset, and whether the metadata is stored inline.
+    ///
+    /// ```rust
+    /// # use ndarray_layout::ArrayLayout;
+    /// let layout = ArrayLayout::<2>::new(&[2, 3], &[3, 1], 0);
+    /// assert_eq!(
+    ///     format!("{layout:?}"),
+    ///     "ArrayLayout { ndim: 2, shape: [2, 3], strides: [3, 1], offset: 0, inline: true }"
+    /// );
+    /// ```
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ArrayLayout")
+            .field("ndim", &self.ndim)
+            .field("shape", &self.shape())
+            .field("strides", &self.strides())
+            .field("offset", &self.offset())
+            .field("inline", &self.is_inline())
+            .finish()
+    }
+}
+
+// SAFETY: the heap-allocated metadata is exclusively owned by the layout and never shared.
+unsafe impl<const N: usize> Send for ArrayLayout<N> {}
+unsafe impl<const N: usize> Sync for ArrayLayout<N> {}
+
 impl<const N: usize> Drop for ArrayLayout<N> {
     fn drop(&mut self) {
         if let Some(ptr) = self.ptr_allocated() {
-            unsafe { dealloc(ptr.cast().as_ptr(), layout(self.ndim)) }
+            unsafe { pool::release(ptr, self.ndim) }
         }
     }
 }
@@ -97,6 +173,102 @@ impl<const N: usize> ArrayLayout<N> {
         ans
     }
 
+    /// Creates a new inline Layout in `const` contexts, so fixed layouts can live in statics.
+    ///
+    /// ```rust
+    /// # use ndarray_layout::ArrayLayout;
+    /// static MAT4: ArrayLayout<4> = ArrayLayout::const_new([4, 4], [16, 4], 0);
+    /// assert_eq!(MAT4, ArrayLayout::new(&[4, 4], &[16, 4], 0));
+    /// assert!(MAT4.is_inline());
+    /// ```
+    ///
+    /// Fails to compile if `D` exceeds the inline capacity `N`.
+    pub const fn const_new<const D: usize>(
+        shape: [usize; D],
+        strides: [isize; D],
+        offset: isize,
+    ) -> Self {
+        const { assert!(D <= N, "dimensions exceed inline capacity") }
+        // shape and strides are packed back-to-back across `head` and `tail`
+        let mut head = [0usize; N];
+        let mut tail = [0isize; N];
+        let mut i = 0;
+        while i < 2 * D {
+            let val = if i < D {
+                shape[i] as isize
+            } else {
+                strides[i - D]
+            };
+            if i < N {
+                head[i] = val as usize
+            } else {
+                tail[i - N] = val
+            }
+            i += 1
+        }
+        Self {
+            ndim: D,
+            content: Union {
+                _inlined: Inlined { offset, head, tail },
+            },
+        }
+    }
+
+    /// Creates a zero-filled layout with the given dimensions, failing instead of
+    /// heap-allocating if `ndim` exceeds the inline capacity `N`.
+    ///
+    /// ```rust
+    /// # use ndarray_layout::{ArrayLayout, LayoutError};
+    /// let layout = ArrayLayout::<2>::try_with_ndim(2).unwrap();
+    /// assert_eq!(layout.shape(), &[0, 0]);
+    /// assert!(layout.is_inline());
+    /// assert_eq!(
+    ///     ArrayLayout::<2>::try_with_ndim(3),
+    ///     Err(LayoutError::InlineCapacityExceeded { ndim: 3, capacity: 2 })
+    /// );
+    /// ```
+    #[inline]
+    pub fn try_with_ndim(ndim: usize) -> Result<Self, LayoutError> {
+        if ndim <= N {
+            Ok(Self::with_ndim(ndim))
+        } else {
+            Err(LayoutError::InlineCapacityExceeded { ndim, capacity: N })
+        }
+    }
+
+    /// Creates a new Layout like [`new`](Self::new), failing instead of heap-allocating
+    /// if the dimensions exceed the inline capacity `N`.
+    ///
+    /// ```rust
+    /// # use ndarray_layout::ArrayLayout;
+    /// assert!(ArrayLayout::<2>::try_new_inline(&[3, 4], &[4, 1], 0).is_ok());
+    /// assert!(ArrayLayout::<2>::try_new_inline(&[2, 3, 4], &[12, 4, 1], 0).is_err());
+    /// ```
+    pub fn try_new_inline(
+        shape: &[usize],
+        strides: &[isize],
+        offset: isize,
+    ) -> Result<Self, LayoutError> {
+        if shape.len() != strides.len() {
+            return Err(LayoutError::LengthMismatch {
+                shape: shape.len(),
+                strides: strides.len(),
+            });
+        }
+        let mut ans = Self::try_with_ndim(shape.len())?;
+        let mut content = ans.content_mut();
+        content.set_offset(offset);
+        content.copy_shape(shape);
+        content.copy_strides(strides);
+        Ok(ans)
+    }
+
+    /// Returns `true` if the metadata is stored inline without heap allocation.
+    #[inline]
+    pub fn is_inline(&self) -> bool {
+        self.ptr_allocated().is_none()
+    }
+
     /// Gets offset.
     #[inline]
     pub const fn ndim(&self) -> usize {
@@ -109,6 +281,19 @@ impl<const N: usize> ArrayLayout<N> {
         self.content().offset()
     }
 
+    /// Sets offset in place.
+    ///
+    /// ```rust
+    /// # use ndarray_layout::ArrayLayout;
+    /// let mut layout = ArrayLayout::<4>::new(&[2, 3], &[3, 1], 0);
+    /// layout.set_offset(12);
+    /// assert_eq!(layout.offset(), 12);
+    /// ```
+    #[inline]
+    pub fn set_offset(&mut self, offset: isize) {
+        self.content_mut().set_offset(offset)
+    }
+
     /// Gets shape.
     #[inline]
     pub fn shape(&self) -> &[usize] {
@@ -121,6 +306,67 @@ impl<const N: usize> ArrayLayout<N> {
         self.content().strides()
     }
 
+    /// Converts the shape to another integer type, such as `u32` for device-side indexing.
+    /// Returns `None` if any dimension overflows.
+    ///
+    /// ```rust
+    /// # use ndarray_layout::ArrayLayout;
+    /// let layout = ArrayLayout::<3>::new(&[2, 3], &[12, -4], 8);
+    /// assert_eq!(layout.shape_as::<u32>(), Some(vec![2, 3]));
+    /// assert_eq!(layout.strides_as::<i32>(), Some(vec![12, -4]));
+    /// assert_eq!(layout.strides_as::<u8>(), None);
+    /// assert_eq!(layout.offset_as::<i32>(), Some(8));
+    /// ```
+    pub fn shape_as<T: TryFrom<usize>>(&self) -> Option<Vec<T>> {
+        self.shape().iter().map(|&d| d.try_into().ok()).collect()
+    }
+
+    /// Converts the strides to another integer type. Returns `None` if any stride overflows.
+    pub fn strides_as<T: TryFrom<isize>>(&self) -> Option<Vec<T>> {
+        self.strides().iter().map(|&s| s.try_into().ok()).collect()
+    }
+
+    /// Converts the offset to another integer type. Returns `None` if it overflows.
+    #[inline]
+    pub fn offset_as<T: TryFrom<isize>>(&self) -> Option<T> {
+        self.offset().try_into().ok()
+    }
+
+    /// Gets the number of elements, which is the product of the shape.
+    ///
+    /// ```rust
+    /// # use ndarray_layout::ArrayLayout;
+    /// let layout = ArrayLayout::<4>::new(&[2, 3, 4], &[12, 4, 1], 0);
+    /// assert_eq!(layout.num_elements(), 24);
+    /// assert_eq!(ArrayLayout::<4>::new(&[usize::MAX, 2, 0], &[0, 0, 0], 0).num_elements(), 0);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the product overflows `usize`.
+    pub fn num_elements(&self) -> usize {
+        let shape = self.shape();
+        if shape.contains(&0) {
+            return 0;
+        }
+        shape
+            .iter()
+            .try_fold(1usize, |acc, &d| acc.checked_mul(d))
+            .expect("number of elements overflows usize")
+    }
+
+    /// Returns `true` if any dimension has zero extent.
+    ///
+    /// ```rust
+    /// # use ndarray_layout::ArrayLayout;
+    /// assert!(!ArrayLayout::<4>::new(&[2, 3], &[3, 1], 0).is_empty());
+    /// assert!(ArrayLayout::<4>::new(&[2, 0], &[3, 1], 0).is_empty());
+    /// ```
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.shape().contains(&0)
+    }
+
     /// Calculate the range of data in bytes to determine the location of the memory area that the tensor needs to access.
     pub fn data_range(&self) -> RangeInclusive<isize> {
         let content = self.content();
@@ -137,13 +383,110 @@ impl<const N: usize> ArrayLayout<N> {
         }
         start..=end
     }
+
+    /// Calculate the minimal size in bytes of a buffer starting at offset 0 that contains every element.
+    ///
+    /// ```rust
+    /// # use ndarray_layout::ArrayLayout;
+    /// let layout = ArrayLayout::<4>::new(&[2, 3, 4], &[48, -16, 4], 32);
+    /// assert_eq!(layout.required_bytes(4), 96);
+    /// assert_eq!(layout.slice(1, 0, 1, 0).required_bytes(4), 0);
+    ///
+    /// let layout = ArrayLayout::<4>::new(&[1, 4], &[16, 4], 8).broadcast(0, 10);
+    /// assert_eq!(layout.required_bytes(4), 24);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the layout accesses a negative offset.
+    pub fn required_bytes(&self, element_size: usize) -> usize {
+        if self.is_empty() {
+            return 0;
+        }
+        let range = self.data_range();
+        assert!(
+            *range.start() >= 0,
+            "layout accesses negative offset {}",
+            range.start()
+        );
+        *range.end() as usize + element_size
+    }
 }
 
+mod analysis;
+#[cfg(feature = "arrow")]
+mod arrow;
+mod builder;
+mod codec;
+mod conv;
+mod cudnn;
+mod descriptor;
+mod distributed;
+mod dtype;
+mod einsum;
+mod error;
+mod ffi;
+mod fixed;
+mod gemm;
+mod halo;
+mod iter;
+mod numpy;
+#[cfg(feature = "onnx")]
+mod onnx;
+mod padded;
+mod pipeline;
+mod plan;
+mod pool;
+mod portable;
+mod read;
+mod shader;
+mod shared;
+mod swizzle;
+mod text;
 mod transform;
-pub use transform::{BroadcastArg, IndexArg, SliceArg, Split, TileArg};
+mod typed;
+
+pub use analysis::{BoundsError, CacheFootprint};
+#[cfg(feature = "arrow")]
+pub use arrow::FixedShapeTensor;
+pub use builder::LayoutBuilder;
+pub use codec::DecodeError;
+pub use conv::{ConvArg, ConvPadding};
+pub use cudnn::{CudnnError, CudnnTensorDesc, CUDNN_DIM_MAX};
+pub use descriptor::{CopyDescriptor, DescriptorError};
+pub use distributed::{BlockCyclic, ShardedLayout};
+pub use dtype::DType;
+pub use einsum::{Einsum, EinsumError};
+pub use error::LayoutError;
+pub use ffi::FfiLayout;
+pub use fixed::StaticLayout;
+pub use gemm::GemmView;
+pub use halo::{Halo, HaloFace};
+#[cfg(feature = "rayon")]
+pub use iter::ParLayouts;
+pub use iter::{Offsets, ShapeMismatch, Tiles, ZipOffsets};
+pub use numpy::{ArrayInterface, NpyError, NpyHeader};
+#[cfg(feature = "onnx")]
+pub use onnx::{OnnxDim, PartialShape};
+pub use padded::PaddedLayout;
+pub use pipeline::{TrackedLayout, Transform};
+pub use plan::{Loop, LoopNest, MemcpyChunk, TransposeTiles};
+pub use portable::{PortableError, PortableLayout};
+pub use read::LayoutRead;
+pub use shader::{ShaderLang, ShaderLayout, UniformRules};
+pub use shared::SharedLayout;
+pub use swizzle::{Swizzle, SwizzledLayout};
+pub use text::ParseLayoutError;
+pub use transform::{
+    invert_permutation, AxisIter, BroadcastArg, BroadcastError, Chunks, GatherPlan, Im2colArg,
+    IndexArg, MergeError, PermuteError, ReshapeDim, SliceArg, SliceExpr, SliceExprError, SliceItem,
+    Split, SplitGrid, TileArg,
+};
+pub use typed::TypedLayout;
 
 use std::{
-    alloc::{alloc, dealloc, Layout},
+    fmt,
+    hash::{Hash, Hasher},
     iter::zip,
     ops::RangeInclusive,
     ptr::{copy_nonoverlapping, NonNull},
@@ -188,11 +531,15 @@ impl<const N: usize> ArrayLayout<N> {
             ndim,
             content: if ndim <= N {
                 Union {
-                    _inlined: (0, [0; N], [0; N]),
+                    _inlined: Inlined {
+                        offset: 0,
+                        head: [0; N],
+                        tail: [0; N],
+                    },
                 }
             } else {
                 Union {
-                    ptr: unsafe { NonNull::new_unchecked(alloc(layout(ndim)).cast()) },
+                    ptr: pool::allocate(ndim),
                 }
             },
         }
@@ -263,7 +610,29 @@ impl Content<true> {
     }
 }
 
-#[inline]
-fn layout(ndim: usize) -> Layout {
-    Layout::array::<usize>(1 + ndim * 2).unwrap()
+#[test]
+fn test_scalar() {
+    let scalar = ArrayLayout::<2>::new(&[], &[], 8);
+    assert_eq!(scalar.merge_many(&[]), Some(scalar.clone()));
+    assert_eq!(scalar.flatten(..), Some(scalar.clone()));
+    assert_eq!(scalar.coalesce(), scalar);
+    assert_eq!(scalar.squeeze(), scalar);
+    assert_eq!(scalar.index_many(&[]), scalar);
+    assert_eq!(scalar.slice_many(&[]), scalar);
+    assert_eq!(scalar.tile_many(&[]), scalar);
+    assert_eq!(scalar.transpose(&[]), scalar);
+    assert_eq!(scalar.reshape(&[]), Some(scalar.clone()));
+    assert_eq!(scalar.reshape(&[1, 1]).unwrap().shape(), &[1, 1]);
+    assert_eq!(
+        ArrayLayout::<2>::new(&[1, 1], &[4, 4], 8).reshape(&[]),
+        Some(scalar.clone())
+    );
+    assert_eq!(scalar.offsets().collect::<Vec<_>>(), [8]);
+    assert_eq!(scalar.data_range(), 8..=8);
+    assert!(!scalar.is_empty());
+    assert_eq!(scalar.required_bytes(4), 12);
+    assert!(scalar.is_c_contiguous(4));
+    assert_eq!(scalar.offset_of(&[]), 8);
+    assert_eq!(scalar.indices_of(8), Some(vec![]));
+    assert_eq!(scalar.to_string().parse(), Ok(scalar));
 }
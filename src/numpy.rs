@@ -0,0 +1,229 @@
+use crate::{ArrayLayout, Endian};
+
+/// `.npy` 文件的魔数。
+const MAGIC: &[u8] = b"\x93NUMPY";
+
+/// NumPy 数组接口中与布局相关的字段。
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct ArrayInterface {
+    /// 形状。
+    pub shape: Vec<usize>,
+    /// 以字节为单位的步长，行优先紧密排布时为 `None`。
+    pub strides: Option<Vec<isize>>,
+    /// 首个元素相对数据指针的字节偏移。
+    pub offset: isize,
+}
+
+/// `.npy` 文件头。
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct NpyHeader {
+    /// 数据类型描述，如 `"<f4"`。
+    pub descr: String,
+    /// 数据是否按列优先排布。
+    pub fortran_order: bool,
+    /// 形状。
+    pub shape: Vec<usize>,
+}
+
+/// `.npy` 文件头解析错误。
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum NpyError {
+    /// 魔数不匹配。
+    InvalidMagic,
+    /// 不支持的格式版本。
+    UnsupportedVersion(u8, u8),
+    /// 数据提前结束。
+    UnexpectedEnd,
+    /// 文件头缺少或无法解析指定的字段。
+    InvalidField(&'static str),
+}
+
+impl<const N: usize> ArrayLayout<N> {
+    /// 导出 NumPy 数组接口的形状、步长和偏移。
+    ///
+    /// ```rust
+    /// # use ndarray_layout::{ArrayLayout, Endian};
+    /// let layout = ArrayLayout::<3>::new_contiguous(&[2, 3], Endian::BigEndian, 4);
+    /// let interface = layout.array_interface(4);
+    /// assert_eq!(interface.strides, None);
+    ///
+    /// let interface = layout.transpose(&[1, 0]).array_interface(4);
+    /// assert_eq!(interface.shape, [3, 2]);
+    /// assert_eq!(interface.strides, Some(vec![4, 12]));
+    /// assert!(ArrayLayout::<3>::from_array_interface(&interface, 4) == layout.transpose(&[1, 0]));
+    /// ```
+    pub fn array_interface(&self, element_size: usize) -> ArrayInterface {
+        ArrayInterface {
+            shape: self.shape().to_vec(),
+            strides: if self.is_c_contiguous(element_size) {
+                None
+            } else {
+                Some(self.strides().to_vec())
+            },
+            offset: self.offset(),
+        }
+    }
+
+    /// 从 NumPy 数组接口的字段构造布局。
+    pub fn from_array_interface(interface: &ArrayInterface, element_size: usize) -> Self {
+        let mut ans = match &interface.strides {
+            Some(strides) => Self::new(&interface.shape, strides, 0),
+            None => Self::new_contiguous(&interface.shape, Endian::BigEndian, element_size),
+        };
+        ans.content_mut().set_offset(interface.offset);
+        ans
+    }
+
+    /// 为紧密排布的布局生成 `.npy` 文件头，不是紧密排布时返回 `None`。
+    ///
+    /// ```rust
+    /// # use ndarray_layout::{ArrayLayout, Endian, NpyHeader};
+    /// let layout = ArrayLayout::<3>::new_contiguous(&[2, 3], Endian::LittleEndian, 4);
+    /// let header = layout.npy_header("<f4", 4).unwrap();
+    /// assert!(header.fortran_order);
+    ///
+    /// let bytes = header.to_bytes();
+    /// assert_eq!(bytes.len(), 128);
+    /// assert!(bytes.starts_with(b"\x93NUMPY\x01\x00\x76\x00{'descr': '<f4', 'fortran_order': True, 'shape': (2, 3), }"));
+    /// assert_eq!(NpyHeader::parse(&bytes), Ok((header, 128)));
+    ///
+    /// assert!(layout.slice(0, 0, 1, 1).npy_header("<f4", 4).is_none());
+    /// ```
+    pub fn npy_header(&self, descr: &str, element_size: usize) -> Option<NpyHeader> {
+        let fortran_order = if self.is_c_contiguous(element_size) {
+            false
+        } else if self.is_f_contiguous(element_size) {
+            true
+        } else {
+            return None;
+        };
+        Some(NpyHeader {
+            descr: descr.into(),
+            fortran_order,
+            shape: self.shape().to_vec(),
+        })
+    }
+}
+
+impl NpyHeader {
+    /// 文件头对应的布局，偏移为 0，表示数据紧接在文件头之后。
+    ///
+    /// ```rust
+    /// # use ndarray_layout::NpyHeader;
+    /// let header = NpyHeader {
+    ///     descr: "<f8".into(),
+    ///     fortran_order: true,
+    ///     shape: vec![2, 3],
+    /// };
+    /// assert_eq!(header.element_size(), Some(8));
+    /// assert_eq!(header.layout::<2>(8).strides(), &[8, 16]);
+    /// ```
+    pub fn layout<const N: usize>(&self, element_size: usize) -> ArrayLayout<N> {
+        let endian = if self.fortran_order {
+            Endian::LittleEndian
+        } else {
+            Endian::BigEndian
+        };
+        ArrayLayout::new_contiguous(&self.shape, endian, element_size)
+    }
+
+    /// 从简单类型描述中解析元素的字节数，如 `"<f4"` 为 4。
+    pub fn element_size(&self) -> Option<usize> {
+        let descr = self.descr.trim_start_matches(['<', '>', '|', '=']);
+        let digits = descr.trim_start_matches(|c: char| c.is_ascii_alphabetic());
+        digits.parse().ok()
+    }
+
+    /// 编码为完整的文件头，包括魔数、版本号和长度，总长度对齐到 64 字节。
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let shape = match &*self.shape {
+            [d] => format!("({d},)"),
+            shape => {
+                let dims = shape.iter().map(|d| d.to_string()).collect::<Vec<_>>();
+                format!("({})", dims.join(", "))
+            }
+        };
+        let dict = format!(
+            "{{'descr': '{}', 'fortran_order': {}, 'shape': {shape}, }}",
+            self.descr,
+            if self.fortran_order { "True" } else { "False" },
+        );
+
+        // 文件头以换行结尾，总长度对齐到 64 字节，长度超过 u16 时使用 2.0 版本
+        let padded = |prefix: usize| (prefix + dict.len() + 1).next_multiple_of(64) - prefix;
+        let (version, prefix) = if padded(MAGIC.len() + 4) <= u16::MAX as usize {
+            (1, MAGIC.len() + 4)
+        } else {
+            (2, MAGIC.len() + 6)
+        };
+        let len = padded(prefix);
+
+        let mut ans = Vec::with_capacity(prefix + len);
+        ans.extend_from_slice(MAGIC);
+        ans.extend_from_slice(&[version, 0]);
+        if version == 1 {
+            ans.extend_from_slice(&(len as u16).to_le_bytes());
+        } else {
+            ans.extend_from_slice(&(len as u32).to_le_bytes());
+        }
+        ans.extend_from_slice(dict.as_bytes());
+        ans.resize(prefix + len - 1, b' ');
+        ans.push(b'\n');
+        ans
+    }
+
+    /// 解析文件头，同时返回文件头的总字节数，即数据的起始位置。
+    pub fn parse(bytes: &[u8]) -> Result<(Self, usize), NpyError> {
+        let rest = bytes.strip_prefix(MAGIC).ok_or(NpyError::InvalidMagic)?;
+        let (prefix, len) = match *rest {
+            [1, 0, a, b, ..] => (MAGIC.len() + 4, u16::from_le_bytes([a, b]) as usize),
+            [2 | 3, 0, a, b, c, d, ..] => {
+                (MAGIC.len() + 6, u32::from_le_bytes([a, b, c, d]) as usize)
+            }
+            [major, minor, _, _, ..] => return Err(NpyError::UnsupportedVersion(major, minor)),
+            _ => return Err(NpyError::UnexpectedEnd),
+        };
+        let dict = bytes
+            .get(prefix..prefix + len)
+            .ok_or(NpyError::UnexpectedEnd)?;
+        let dict = std::str::from_utf8(dict).map_err(|_| NpyError::InvalidField("header"))?;
+
+        let descr = field(dict, "descr")?
+            .strip_prefix('\'')
+            .and_then(|s| s.split_once('\''))
+            .map(|(descr, _)| descr.to_string())
+            .ok_or(NpyError::InvalidField("descr"))?;
+        let fortran_order = match field(dict, "fortran_order")? {
+            s if s.starts_with("True") => true,
+            s if s.starts_with("False") => false,
+            _ => return Err(NpyError::InvalidField("fortran_order")),
+        };
+        let shape = field(dict, "shape")?
+            .strip_prefix('(')
+            .and_then(|s| s.split_once(')'))
+            .ok_or(NpyError::InvalidField("shape"))?
+            .0
+            .split(',')
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .map(|s| s.parse().map_err(|_| NpyError::InvalidField("shape")))
+            .collect::<Result<_, _>>()?;
+
+        Ok((
+            Self {
+                descr,
+                fortran_order,
+                shape,
+            },
+            prefix + len,
+        ))
+    }
+}
+
+/// 找到字典中指定键对应的值的起始位置。
+fn field<'a>(dict: &'a str, key: &'static str) -> Result<&'a str, NpyError> {
+    dict.split_once(&format!("'{key}'"))
+        .and_then(|(_, rest)| rest.trim_start().strip_prefix(':'))
+        .map(str::trim_start)
+        .ok_or(NpyError::InvalidField(key))
+}
@@ -0,0 +1,108 @@
+use crate::{ArrayLayout, Endian};
+
+/// ONNX `TensorShapeProto.Dimension` 的取值。
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub enum OnnxDim {
+    /// 已知长度，对应 `dim_value`。
+    Value(i64),
+    /// 符号长度，对应 `dim_param`。
+    Param(String),
+    /// 未设置的长度。
+    Unknown,
+}
+
+/// 含有符号或未知长度、无法确定布局的形状。
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct PartialShape {
+    /// 每一阶的长度，已知的长度为 `Some`。
+    pub dims: Vec<Option<usize>>,
+    /// 每个符号长度的阶号和名字。
+    pub params: Vec<(usize, String)>,
+}
+
+impl<const N: usize> ArrayLayout<N> {
+    /// 从 ONNX `TensorShapeProto` 的维度构造行优先紧密排布的布局。
+    /// 存在符号、未知或负数长度时返回部分形状。
+    ///
+    /// ```rust
+    /// # use ndarray_layout::{ArrayLayout, OnnxDim::*, PartialShape};
+    /// let layout = ArrayLayout::<4>::from_onnx_shape(&[Value(2), Value(3)], 4).unwrap();
+    /// assert_eq!(layout.shape(), &[2, 3]);
+    /// assert_eq!(layout.strides(), &[12, 4]);
+    ///
+    /// let partial = ArrayLayout::<4>::from_onnx_shape(&[Param("batch".into()), Value(3), Unknown], 4);
+    /// assert_eq!(
+    ///     partial.err(),
+    ///     Some(PartialShape {
+    ///         dims: vec![None, Some(3), None],
+    ///         params: vec![(0, "batch".into())],
+    ///     })
+    /// );
+    /// ```
+    pub fn from_onnx_shape(dims: &[OnnxDim], element_size: usize) -> Result<Self, PartialShape> {
+        let mut partial = PartialShape {
+            dims: Vec::with_capacity(dims.len()),
+            params: Vec::new(),
+        };
+        for (i, dim) in dims.iter().enumerate() {
+            partial.dims.push(match dim {
+                &OnnxDim::Value(d) => usize::try_from(d).ok(),
+                OnnxDim::Param(name) => {
+                    partial.params.push((i, name.clone()));
+                    None
+                }
+                OnnxDim::Unknown => None,
+            })
+        }
+        match partial.dims.iter().copied().collect::<Option<Vec<_>>>() {
+            Some(shape) => Ok(Self::new_contiguous(
+                &shape,
+                Endian::BigEndian,
+                element_size,
+            )),
+            None => Err(partial),
+        }
+    }
+
+    /// 从 ONNX `TensorProto` 的 `dims` 构造行优先紧密排布的布局，存在负数长度时返回 `None`。
+    pub fn from_onnx_dims(dims: &[i64], element_size: usize) -> Option<Self> {
+        let shape = dims
+            .iter()
+            .map(|&d| usize::try_from(d).ok())
+            .collect::<Option<Vec<_>>>()?;
+        Some(Self::new_contiguous(
+            &shape,
+            Endian::BigEndian,
+            element_size,
+        ))
+    }
+
+    /// 导出 ONNX `TensorShapeProto` 的维度。
+    #[inline]
+    pub fn to_onnx_shape(&self) -> Vec<OnnxDim> {
+        self.shape()
+            .iter()
+            .map(|&d| OnnxDim::Value(d as _))
+            .collect()
+    }
+
+    /// 导出 ONNX `TensorProto` 的 `dims`。
+    /// `TensorProto` 只能表示行优先紧密排布的数据，其他布局返回 `None`。
+    ///
+    /// ```rust
+    /// # use ndarray_layout::{ArrayLayout, Endian};
+    /// let layout = ArrayLayout::<4>::new_contiguous(&[2, 3], Endian::BigEndian, 4);
+    /// assert_eq!(layout.to_onnx_dims(4), Some(vec![2, 3]));
+    /// assert_eq!(layout.transpose(&[1, 0]).to_onnx_dims(4), None);
+    /// assert!(ArrayLayout::<4>::from_onnx_dims(&[2, 3], 4).unwrap() == layout);
+    /// ```
+    pub fn to_onnx_dims(&self, element_size: usize) -> Option<Vec<i64>> {
+        if !self.is_c_contiguous(element_size) {
+            return None;
+        }
+        self.shape()
+            .iter()
+            .map(|&d| i64::try_from(d).ok())
+            .collect()
+    }
+}
@@ -0,0 +1,90 @@
+use crate::ArrayLayout;
+use std::ops::Range;
+
+/// 带有虚拟填充的布局。
+///
+/// 填充不占用存储空间，只在逻辑上扩大张量的形状。
+/// 逻辑索引落在填充区域时不对应任何数据，否则映射到物理布局中的偏移。
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct PaddedLayout<const N: usize = 2> {
+    layout: ArrayLayout<N>,
+    pads: Vec<(usize, usize)>,
+}
+
+impl<const N: usize> PaddedLayout<N> {
+    /// 为物理布局的每一阶指定前后填充的长度。
+    ///
+    /// ```rust
+    /// # use ndarray_layout::{ArrayLayout, PaddedLayout};
+    /// let layout = ArrayLayout::<2>::new(&[3, 4], &[4, 1], 0);
+    /// let padded = PaddedLayout::new(layout, &[(1, 1), (2, 0)]);
+    /// assert_eq!(padded.shape(), [5, 6]);
+    /// assert_eq!(padded.offset_of(&[0, 3]), None);
+    /// assert_eq!(padded.offset_of(&[1, 2]), Some(0));
+    /// assert_eq!(padded.offset_of(&[3, 5]), Some(11));
+    /// ```
+    pub fn new(layout: ArrayLayout<N>, pads: &[(usize, usize)]) -> Self {
+        assert_eq!(
+            layout.ndim(),
+            pads.len(),
+            "layout and pads must have the same length"
+        );
+        Self {
+            layout,
+            pads: pads.to_vec(),
+        }
+    }
+
+    /// 物理布局。
+    #[inline]
+    pub fn layout(&self) -> &ArrayLayout<N> {
+        &self.layout
+    }
+
+    /// 每一阶前后填充的长度。
+    #[inline]
+    pub fn pads(&self) -> &[(usize, usize)] {
+        &self.pads
+    }
+
+    /// 包含填充的逻辑形状。
+    pub fn shape(&self) -> Vec<usize> {
+        self.layout
+            .shape()
+            .iter()
+            .zip(&self.pads)
+            .map(|(&d, &(pre, post))| pre + d + post)
+            .collect()
+    }
+
+    /// 指定阶上对应真实数据的逻辑索引范围。
+    #[inline]
+    pub fn interior(&self, axis: usize) -> Range<usize> {
+        let pre = self.pads[axis].0;
+        pre..pre + self.layout.shape()[axis]
+    }
+
+    /// 判断逻辑索引是否落在填充区域。
+    #[inline]
+    pub fn is_padding(&self, indices: &[usize]) -> bool {
+        self.offset_of(indices).is_none()
+    }
+
+    /// 将逻辑索引映射到物理偏移，落在填充区域时返回 `None`。
+    pub fn offset_of(&self, indices: &[usize]) -> Option<isize> {
+        assert_eq!(indices.len(), self.pads.len());
+        let mut offset = self.layout.offset();
+        for (axis, &i) in indices.iter().enumerate() {
+            let range = self.interior(axis);
+            assert!(
+                i < range.end + self.pads[axis].1,
+                "index {i} out of range for axis {axis}"
+            );
+            if !range.contains(&i) {
+                return None;
+            }
+            offset += (i - range.start) as isize * self.layout.strides()[axis];
+        }
+        Some(offset)
+    }
+}
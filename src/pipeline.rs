@@ -0,0 +1,598 @@
+use crate::{ArrayLayout, BroadcastArg, Endian, IndexArg, LayoutError, SliceArg, TileArg};
+use std::{iter::zip, ops::Range};
+
+/// 以数据表示的单步布局变换，可以保存并重放。
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub enum Transform {
+    /// 切片变换，见 [`slice`](ArrayLayout::slice)。
+    Slice(SliceArg),
+    /// 索引变换，见 [`index`](ArrayLayout::index)。
+    Index(IndexArg),
+    /// 分块变换，见 [`tile_be`](ArrayLayout::tile_be) 和 [`tile_le`](ArrayLayout::tile_le)。
+    Tile {
+        /// 分块的轴。
+        axis: usize,
+        /// 分块的顺序。
+        endian: Endian,
+        /// 分块的大小。
+        tiles: Vec<usize>,
+    },
+    /// 合并变换，见 [`merge`](ArrayLayout::merge)。
+    Merge(Range<usize>),
+    /// 转置变换，见 [`transpose`](ArrayLayout::transpose)。
+    Transpose(Vec<usize>),
+    /// 广播变换，见 [`broadcast`](ArrayLayout::broadcast)。
+    Broadcast(BroadcastArg),
+    /// 索引变换的逆变换，在 `axis` 处插入长度为 `dim`、步长为 `stride` 的阶，
+    /// 并使原布局成为新阶上第 `index` 项。
+    Insert {
+        /// 插入后新阶的位置。
+        axis: usize,
+        /// 原布局在新阶上的索引。
+        index: usize,
+        /// 新阶的长度。
+        dim: usize,
+        /// 新阶的步长。
+        stride: isize,
+    },
+}
+
+impl<const N: usize> ArrayLayout<N> {
+    /// 依次应用一系列变换，任何一步失败时返回错误。
+    ///
+    /// ```rust
+    /// # use ndarray_layout::{ArrayLayout, Endian, IndexArg, SliceArg, Transform};
+    /// let layout = ArrayLayout::<3>::new(&[2, 3, 4], &[12, 4, 1], 0);
+    /// let pipeline = [
+    ///     Transform::Transpose(vec![1, 0]),
+    ///     Transform::Slice(SliceArg { axis: 2, start: 1, step: 2, len: 2 }),
+    ///     Transform::Index(IndexArg { axis: 0, index: 2 }),
+    ///     Transform::Tile { axis: 0, endian: Endian::BigEndian, tiles: vec![2, 1] },
+    /// ];
+    /// let layout = layout.apply(&pipeline).unwrap();
+    /// assert_eq!(layout.shape(), &[2, 1, 2]);
+    /// assert_eq!(layout.strides(), &[12, 12, 2]);
+    /// assert_eq!(layout.offset(), 9);
+    ///
+    /// let layout = ArrayLayout::<3>::new(&[2, 3, 4], &[12, 4, 1], 0);
+    /// assert!(layout.apply(&[Transform::Merge(1..4)]).is_err());
+    /// ```
+    pub fn apply(&self, transforms: &[Transform]) -> Result<Self, LayoutError> {
+        transforms
+            .iter()
+            .try_fold(self.clone(), |layout, t| layout.apply_one(t))
+    }
+
+    /// 应用单步变换。
+    pub fn apply_one(&self, transform: &Transform) -> Result<Self, LayoutError> {
+        match transform {
+            Transform::Slice(arg) => self.try_slice_many(std::slice::from_ref(arg)),
+            Transform::Index(arg) => self.try_index_many(std::slice::from_ref(arg)),
+            &Transform::Tile {
+                axis,
+                endian,
+                ref tiles,
+            } => self.try_tile_many(&[TileArg {
+                axis,
+                endian,
+                tiles,
+            }]),
+            Transform::Merge(range) => {
+                let ndim = self.ndim;
+                if range.end > ndim {
+                    return Err(LayoutError::AxisOutOfRange {
+                        axis: range.end - 1,
+                        ndim,
+                    });
+                }
+                Ok(self.try_merge_many(std::slice::from_ref(range))?)
+            }
+            Transform::Transpose(perm) => self.try_transpose(perm),
+            &Transform::Broadcast(BroadcastArg { axis, times }) => {
+                let ndim = self.ndim;
+                if axis >= ndim {
+                    return Err(LayoutError::AxisOutOfRange { axis, ndim });
+                }
+                let (dim, stride) = (self.shape()[axis], self.strides()[axis]);
+                if dim != 1 && stride != 0 {
+                    return Err(LayoutError::InvalidBroadcast { axis, dim, stride });
+                }
+                Ok(self.broadcast(axis, times))
+            }
+            &Transform::Insert {
+                axis,
+                index,
+                dim,
+                stride,
+            } => {
+                let ndim = self.ndim;
+                if axis > ndim {
+                    return Err(LayoutError::AxisOutOfRange { axis, ndim });
+                }
+                if index >= dim {
+                    return Err(LayoutError::IndexOutOfRange { axis, index, dim });
+                }
+                let mut shape = self.shape().to_vec();
+                let mut strides = self.strides().to_vec();
+                shape.insert(axis, dim);
+                strides.insert(axis, stride);
+                let offset = self.offset() - index as isize * stride;
+                Ok(Self::new(&shape, &strides, offset))
+            }
+        }
+    }
+}
+
+impl Transform {
+    /// 计算逆变换，使得 `input` 依次经过该变换和逆变换后保持不变。
+    ///
+    /// 转置、分块与合并、覆盖整个阶且步长为 ±1 的切片是可逆的；
+    /// 索引与 [`Insert`](Transform::Insert) 互逆，插入阶的长度和步长取自 `input`。
+    /// 其他变换或无法还原时返回 `None`。
+    ///
+    /// ```rust
+    /// # use ndarray_layout::{ArrayLayout, Endian, IndexArg, SliceArg, Transform};
+    /// let input = ArrayLayout::<3>::new(&[2, 3, 4], &[12, 4, 1], 0);
+    ///
+    /// let merge = Transform::Merge(1..3);
+    /// let inverse = merge.inverse(&input).unwrap();
+    /// assert_eq!(inverse, Transform::Tile { axis: 1, endian: Endian::BigEndian, tiles: vec![3, 4] });
+    ///
+    /// let flip = Transform::Slice(SliceArg { axis: 2, start: 3, step: -1, len: 4 });
+    /// assert_eq!(flip.inverse(&input), Some(flip.clone()));
+    ///
+    /// let narrow = Transform::Slice(SliceArg { axis: 2, start: 1, step: 1, len: 2 });
+    /// assert_eq!(narrow.inverse(&input), None);
+    ///
+    /// let index = Transform::Index(IndexArg { axis: 1, index: 2 });
+    /// assert_eq!(
+    ///     index.inverse(&input),
+    ///     Some(Transform::Insert { axis: 1, index: 2, dim: 3, stride: 4 })
+    /// );
+    /// ```
+    pub fn inverse<const N: usize>(&self, input: &ArrayLayout<N>) -> Option<Transform> {
+        let output = input.apply_one(self).ok()?;
+        let candidates = match self {
+            Transform::Merge(range) => {
+                let tiles = input.shape().get(range.clone())?.to_vec();
+                let axis = range.start;
+                vec![
+                    Transform::Tile {
+                        axis,
+                        endian: Endian::BigEndian,
+                        tiles: tiles.clone(),
+                    },
+                    Transform::Tile {
+                        axis,
+                        endian: Endian::LittleEndian,
+                        tiles,
+                    },
+                ]
+            }
+            &Transform::Slice(SliceArg { axis, .. }) => {
+                let d = input.shape()[axis];
+                vec![
+                    Transform::Slice(SliceArg {
+                        axis,
+                        start: 0,
+                        step: 1,
+                        len: d,
+                    }),
+                    Transform::Slice(SliceArg {
+                        axis,
+                        start: d.checked_sub(1)?,
+                        step: -1,
+                        len: d,
+                    }),
+                ]
+            }
+            &Transform::Index(IndexArg { axis, index }) => vec![Transform::Insert {
+                axis,
+                index,
+                dim: *input.shape().get(axis)?,
+                stride: input.strides()[axis],
+            }],
+            _ => vec![self.inverse_from_output()?],
+        };
+        candidates
+            .into_iter()
+            .find(|t| output.apply_one(t).is_ok_and(|layout| layout == *input))
+    }
+
+    /// 只根据变换本身确定的逆变换，假设切片覆盖整个阶。
+    fn inverse_from_output(&self) -> Option<Transform> {
+        match self {
+            &Transform::Insert { axis, index, .. } => {
+                Some(Transform::Index(IndexArg { axis, index }))
+            }
+            Transform::Transpose(perm) => {
+                let mut sorted = perm.clone();
+                sorted.sort_unstable();
+                let inv = sorted
+                    .iter()
+                    .map(|axis| perm.iter().position(|p| p == axis).map(|j| sorted[j]))
+                    .collect::<Option<_>>()?;
+                Some(Transform::Transpose(inv))
+            }
+            Transform::Tile { axis, tiles, .. } if !tiles.is_empty() => {
+                Some(Transform::Merge(*axis..axis + tiles.len()))
+            }
+            &Transform::Slice(SliceArg {
+                axis,
+                start,
+                step,
+                len,
+            }) => match step {
+                1 if start == 0 => Some(Transform::Slice(SliceArg {
+                    axis,
+                    start: 0,
+                    step: 1,
+                    len,
+                })),
+                -1 if start + 1 == len => Some(Transform::Slice(SliceArg {
+                    axis,
+                    start,
+                    step: -1,
+                    len,
+                })),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    /// 将变换结果中的索引映射回变换前的索引，无法确定时返回 `None`。
+    ///
+    /// 合并变换需要知道合并前的形状，因此总是返回 `None`。
+    ///
+    /// ```rust
+    /// # use ndarray_layout::{Endian, IndexArg, Transform};
+    /// let index = Transform::Index(IndexArg { axis: 1, index: 2 });
+    /// assert_eq!(index.input_indices(&[1, 3]), Some(vec![1, 2, 3]));
+    ///
+    /// let tile = Transform::Tile { axis: 0, endian: Endian::BigEndian, tiles: vec![2, 3] };
+    /// assert_eq!(tile.input_indices(&[1, 2, 5]), Some(vec![5, 5]));
+    ///
+    /// let transpose = Transform::Transpose(vec![1, 2, 0]);
+    /// assert_eq!(transpose.input_indices(&[7, 8, 9]), Some(vec![9, 7, 8]));
+    /// ```
+    pub fn input_indices(&self, output: &[usize]) -> Option<Vec<usize>> {
+        let mut ans = output.to_vec();
+        match *self {
+            Transform::Slice(SliceArg {
+                axis, start, step, ..
+            }) => {
+                let i = start as isize + *output.get(axis)? as isize * step;
+                ans[axis] = usize::try_from(i).ok()?;
+            }
+            Transform::Index(IndexArg { axis, index }) => {
+                if axis > ans.len() {
+                    return None;
+                }
+                ans.insert(axis, index)
+            }
+            Transform::Tile {
+                axis,
+                endian,
+                ref tiles,
+            } => {
+                let parts = output.get(axis..axis + tiles.len())?;
+                let pairs = parts.iter().zip(tiles);
+                let i = match endian {
+                    Endian::BigEndian => pairs.fold(0, |acc, (&i, &t)| acc * t + i),
+                    Endian::LittleEndian => pairs.rev().fold(0, |acc, (&i, &t)| acc * t + i),
+                };
+                ans.splice(axis..axis + tiles.len(), [i]);
+            }
+            Transform::Merge(_) => return None,
+            Transform::Transpose(ref perm) => {
+                let mut sorted = perm.clone();
+                sorted.sort_unstable();
+                for (&i, &j) in sorted.iter().zip(perm) {
+                    *ans.get_mut(j)? = *output.get(i)?;
+                }
+            }
+            Transform::Broadcast(BroadcastArg { axis, .. }) => *ans.get_mut(axis)? = 0,
+            // 只有插入阶上第 index 项来自变换前的布局
+            Transform::Insert { axis, index, .. } => {
+                if *output.get(axis)? != index {
+                    return None;
+                }
+                ans.remove(axis);
+            }
+        }
+        Some(ans)
+    }
+}
+
+impl<const N: usize> ArrayLayout<N> {
+    /// 假设当前布局是 `transform` 的结果，还原变换前的布局。
+    ///
+    /// 只支持逆变换由变换本身确定的情况，即转置、分块、[`Insert`](Transform::Insert)，
+    /// 以及从头开始的正向切片或从末尾开始的反向切片，切片被假设为覆盖了整个阶。
+    /// 索引变换移除的阶的长度和步长无法从结果中得知，因此不能还原，需要改用 [`Transform::inverse`]。
+    /// 其他情况同样返回 `None`。
+    ///
+    /// ```rust
+    /// # use ndarray_layout::{ArrayLayout, Endian, Transform};
+    /// let input = ArrayLayout::<3>::new(&[2, 6], &[6, 1], 0);
+    /// let tile = Transform::Tile { axis: 1, endian: Endian::BigEndian, tiles: vec![2, 3] };
+    /// let output = input.apply_one(&tile).unwrap();
+    /// assert!(output.undo(&tile).unwrap() == input);
+    ///
+    /// let transpose = Transform::Transpose(vec![1, 2, 0]);
+    /// let output = output.apply_one(&transpose).unwrap();
+    /// assert_eq!(output.shape(), &[2, 3, 2]);
+    /// assert!(output.undo(&transpose).unwrap() == input.apply_one(&tile).unwrap());
+    /// ```
+    pub fn undo(&self, transform: &Transform) -> Option<Self> {
+        self.apply_one(&transform.inverse_from_output()?).ok()
+    }
+}
+
+impl<const N: usize> ArrayLayout<N> {
+    /// 寻找将当前布局变为 `target` 的一系列不移动数据的变换，需要拷贝数据时返回 `None`。
+    ///
+    /// 支持转置、分块与合并构成的重塑、广播以及长度为 1 的阶的增删，两个布局的偏移必须相同。
+    /// 结果与 `target` 的映射相同，但长度为 1 的阶的步长可能不同，见 [`maps_identically`](Self::maps_identically)。
+    ///
+    /// ```rust
+    /// # use ndarray_layout::{ArrayLayout, Transform};
+    /// let layout = ArrayLayout::<3>::new(&[2, 3, 4], &[12, 4, 1], 0);
+    /// let target = ArrayLayout::<3>::new(&[4, 6], &[1, 4], 0);
+    /// assert_eq!(
+    ///     layout.diff(&target),
+    ///     Some(vec![Transform::Transpose(vec![2, 0, 1]), Transform::Merge(1..3)])
+    /// );
+    ///
+    /// let layout = ArrayLayout::<3>::new(&[2, 3], &[3, 1], 0);
+    /// let target = ArrayLayout::<3>::new(&[3, 2], &[2, 1], 0);
+    /// assert_eq!(layout.diff(&target), None);
+    /// ```
+    pub fn diff(&self, target: &Self) -> Option<Vec<Transform>> {
+        if self == target {
+            return Some(Vec::new());
+        }
+        if self.offset() != target.offset() || self.is_empty() || target.is_empty() {
+            return None;
+        }
+
+        // 长度为 1 或步长为 0 的阶不影响寻址，在源布局中以索引 0 移除，在目标布局中最后插入
+        let real = |layout: &Self| {
+            zip(layout.shape(), layout.strides())
+                .enumerate()
+                .filter(|(_, (&d, &s))| d != 1 && s != 0)
+                .map(|(i, (&d, &s))| (i, d, s))
+                .collect::<Vec<_>>()
+        };
+        let src = real(self);
+        let tgt = real(target);
+
+        let mut steps = Vec::new();
+        let mut removed = 0;
+        for axis in 0..self.ndim {
+            if !src.iter().any(|&(i, ..)| i == axis) {
+                steps.push(Transform::Index(IndexArg {
+                    axis: axis - removed,
+                    index: 0,
+                }));
+                removed += 1;
+            }
+        }
+
+        // 以两个布局所有阶的步长边界将每一阶切分为最细的分块
+        let mut bounds = Vec::new();
+        for &(_, d, s) in src.iter().chain(&tgt) {
+            let lo = s.unsigned_abs();
+            bounds.push(lo);
+            bounds.push(lo.checked_mul(d)?);
+        }
+        bounds.sort_unstable();
+        bounds.dedup();
+        let pieces = |&(_, d, s): &(usize, usize, isize)| {
+            let lo = s.unsigned_abs();
+            let hi = lo * d;
+            let mut ans = Vec::new();
+            let mut a = lo;
+            for &b in bounds.iter().filter(|&&b| lo < b && b <= hi) {
+                if !b.is_multiple_of(a) {
+                    return None;
+                }
+                ans.push((b / a, a as isize * s.signum()));
+                a = b;
+            }
+            ans.reverse();
+            Some(ans)
+        };
+        let src_pieces = src.iter().map(pieces).collect::<Option<Vec<_>>>()?;
+        let tgt_pieces = tgt.iter().map(pieces).collect::<Option<Vec<_>>>()?;
+
+        for (axis, p) in src_pieces.iter().enumerate().rev() {
+            if p.len() > 1 {
+                steps.push(Transform::Tile {
+                    axis,
+                    endian: Endian::BigEndian,
+                    tiles: p.iter().map(|&(d, _)| d).collect(),
+                });
+            }
+        }
+
+        let atoms = src_pieces.concat();
+        let mut used = vec![false; atoms.len()];
+        let mut perm = Vec::with_capacity(atoms.len());
+        for piece in tgt_pieces.iter().flatten() {
+            let j = (0..atoms.len()).find(|&j| !used[j] && atoms[j] == *piece)?;
+            used[j] = true;
+            perm.push(j);
+        }
+        if perm.len() != atoms.len() {
+            return None;
+        }
+        if perm.iter().enumerate().any(|(i, &j)| i != j) {
+            steps.push(Transform::Transpose(perm));
+        }
+
+        let mut pos = atoms.len();
+        for p in tgt_pieces.iter().rev() {
+            pos -= p.len();
+            if p.len() > 1 {
+                steps.push(Transform::Merge(pos..pos + p.len()));
+            }
+        }
+
+        // 插入目标布局中长度为 1 或步长为 0 的阶，真实的阶可能已全部移除，因此不能借助分块插入
+        for (axis, (&dim, &stride)) in zip(target.shape(), target.strides()).enumerate() {
+            if dim == 1 || stride == 0 {
+                steps.push(Transform::Insert {
+                    axis,
+                    index: 0,
+                    dim,
+                    stride,
+                });
+            }
+        }
+
+        let result = self.apply(&steps).ok()?;
+        result.maps_identically(target).then_some(steps)
+    }
+}
+
+/// 记录变换历史的布局。
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct TrackedLayout<const N: usize = 2> {
+    origin: ArrayLayout<N>,
+    layout: ArrayLayout<N>,
+    history: Vec<Transform>,
+}
+
+impl<const N: usize> TrackedLayout<N> {
+    /// 以 `layout` 为起点开始记录。
+    ///
+    /// ```rust
+    /// # use ndarray_layout::{ArrayLayout, IndexArg, TrackedLayout, Transform};
+    /// let mut tracked = TrackedLayout::new(ArrayLayout::<3>::new(&[2, 3, 4], &[12, 4, 1], 0));
+    /// tracked.apply(Transform::Transpose(vec![2, 0])).unwrap();
+    /// tracked.apply(Transform::Index(IndexArg { axis: 1, index: 1 })).unwrap();
+    /// assert!(tracked.apply(Transform::Merge(0..3)).is_err());
+    ///
+    /// assert_eq!(tracked.history().len(), 2);
+    /// assert_eq!(tracked.layout().shape(), &[4, 2]);
+    /// assert_eq!(tracked.layout().strides(), &[1, 12]);
+    /// assert_eq!(tracked.layout().offset(), 4);
+    /// assert!(tracked.origin().apply(tracked.history()).unwrap() == *tracked.layout());
+    /// ```
+    #[inline]
+    pub fn new(layout: ArrayLayout<N>) -> Self {
+        Self {
+            origin: layout.clone(),
+            layout,
+            history: Vec::new(),
+        }
+    }
+
+    /// 开始记录时的布局。
+    #[inline]
+    pub fn origin(&self) -> &ArrayLayout<N> {
+        &self.origin
+    }
+
+    /// 当前的布局。
+    #[inline]
+    pub fn layout(&self) -> &ArrayLayout<N> {
+        &self.layout
+    }
+
+    /// 已应用的变换，按应用的顺序排列。
+    #[inline]
+    pub fn history(&self) -> &[Transform] {
+        &self.history
+    }
+
+    /// 应用一步变换并记录，失败时布局和历史保持不变。
+    pub fn apply(&mut self, transform: Transform) -> Result<&ArrayLayout<N>, LayoutError> {
+        self.layout = self.layout.apply_one(&transform)?;
+        self.history.push(transform);
+        Ok(&self.layout)
+    }
+
+    /// 取出当前的布局，丢弃历史。
+    #[inline]
+    pub fn into_layout(self) -> ArrayLayout<N> {
+        self.layout
+    }
+}
+
+#[test]
+fn test() {
+    let input = ArrayLayout::<3>::new(&[2, 3, 4, 5], &[60, 20, 5, 1], 7);
+    for t in [
+        Transform::Transpose(vec![3, 1, 0]),
+        Transform::Transpose(vec![0, 1, 2, 3]),
+        Transform::Tile {
+            axis: 2,
+            endian: Endian::LittleEndian,
+            tiles: vec![2, 2],
+        },
+        Transform::Merge(0..2),
+        Transform::Slice(SliceArg {
+            axis: 1,
+            start: 2,
+            step: -1,
+            len: 3,
+        }),
+    ] {
+        let inverse = t.inverse(&input).unwrap();
+        let output = input.apply_one(&t).unwrap();
+        assert_eq!(output.apply_one(&inverse).unwrap(), input);
+        if !matches!(t, Transform::Merge(_)) {
+            assert_eq!(output.undo(&t).unwrap(), input);
+        }
+    }
+    let index = Transform::Index(IndexArg { axis: 2, index: 3 });
+    let output = input.apply_one(&index).unwrap();
+    let insert = index.inverse(&input).unwrap();
+    assert_eq!(output.apply_one(&insert).unwrap(), input);
+    assert_eq!(output.undo(&index), None);
+    assert_eq!(input.undo(&insert).unwrap(), output);
+    assert_eq!(insert.input_indices(&[1, 2, 3, 4]), Some(vec![1, 2, 4]));
+    assert_eq!(insert.input_indices(&[1, 2, 0, 4]), None);
+    assert!(output
+        .apply_one(&Transform::Insert {
+            axis: 2,
+            index: 4,
+            dim: 4,
+            stride: 5
+        })
+        .is_err());
+
+    // 没有真实的阶
+    for (layout, target) in [
+        (
+            ArrayLayout::<3>::new(&[1], &[4], 0),
+            ArrayLayout::<3>::new(&[1, 1], &[4, 4], 0),
+        ),
+        (
+            ArrayLayout::<3>::new(&[], &[], 8),
+            ArrayLayout::<3>::new(&[5], &[0], 8),
+        ),
+        (
+            ArrayLayout::<3>::new(&[3, 1], &[0, 2], 8),
+            ArrayLayout::<3>::new(&[], &[], 8),
+        ),
+    ] {
+        let steps = layout.diff(&target).unwrap();
+        assert_eq!(layout.apply(&steps).unwrap(), target);
+    }
+
+    let layout = ArrayLayout::<3>::new(&[3, 1, 2, 4], &[4, 9, 0, -12], 20);
+    for target in [
+        ArrayLayout::<3>::new(&[2, 2, 1, 3, 2], &[-24, -12, 0, 4, 0], 20),
+        ArrayLayout::<3>::new(&[4, 3], &[-12, 4], 20),
+        ArrayLayout::<3>::new(&[1, 4, 3, 1], &[7, -12, 4, 7], 20),
+    ] {
+        let steps = layout.diff(&target).unwrap();
+        assert!(layout.apply(&steps).unwrap().maps_identically(&target));
+    }
+    assert_eq!(layout.diff(&ArrayLayout::new(&[12], &[4], 20)), None);
+    assert_eq!(layout.diff(&ArrayLayout::new(&[4, 3], &[-12, 4], 16)), None);
+}
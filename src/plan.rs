@@ -0,0 +1,218 @@
+use crate::{ArrayLayout, ShapeMismatch};
+use std::cmp::Reverse;
+
+/// 同时遍历 `K` 个形状相同的张量的循环嵌套。
+///
+/// 长度为 1 的阶被移除，其余的阶按步长从大到小排列，使步长最小的阶位于最内层，
+/// 并合并所有张量中都连续的相邻层，层数尽可能少。
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct LoopNest<const K: usize> {
+    /// 每个张量的起始偏移。
+    pub offsets: [isize; K],
+    /// 从外到内的各层循环。
+    pub loops: Vec<Loop<K>>,
+}
+
+/// 循环嵌套中的一层。
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct Loop<const K: usize> {
+    /// 循环次数。
+    pub extent: usize,
+    /// 每个张量在这一层的步长。
+    pub strides: [isize; K],
+}
+
+/// 可以用一次内存拷贝完成的最内层数据块。
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct MemcpyChunk<const N: usize> {
+    /// 数据块包含的元素数量。
+    pub elements: usize,
+    /// 目标张量中数据块起始位置构成的外层布局。
+    pub dst: ArrayLayout<N>,
+    /// 源张量中数据块起始位置构成的外层布局。
+    pub src: ArrayLayout<N>,
+}
+
+/// 分块转置的分块建议。
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct TransposeTiles {
+    /// 分块的两个阶，分别是在源张量和目标张量中步长最小的阶。
+    pub axes: [usize; 2],
+    /// 两个阶上分块的长度。
+    pub tile: [usize; 2],
+}
+
+impl<const K: usize> LoopNest<K> {
+    /// 为若干形状相同的张量生成循环嵌套。排序时优先考虑靠前的张量的步长。
+    ///
+    /// ```rust
+    /// # use ndarray_layout::{ArrayLayout, Endian, Loop, LoopNest};
+    /// let dst = ArrayLayout::<4>::new_contiguous(&[2, 3, 4], Endian::LittleEndian, 4);
+    /// let src = ArrayLayout::<4>::new_contiguous(&[2, 3, 4], Endian::LittleEndian, 2);
+    /// let nest = LoopNest::new([&dst, &src]);
+    /// assert_eq!(nest.offsets, [0, 0]);
+    /// assert_eq!(nest.loops, [Loop { extent: 24, strides: [4, 2] }]);
+    /// ```
+    pub fn new<const N: usize>(layouts: [&ArrayLayout<N>; K]) -> Self {
+        let shape = layouts.first().map_or(&[][..], |l| l.shape());
+        for l in &layouts {
+            assert_eq!(l.shape(), shape, "layouts must have the same shape");
+        }
+
+        let offsets = layouts.map(|l| l.offset());
+        if shape.contains(&0) {
+            return Self {
+                offsets,
+                loops: vec![Loop {
+                    extent: 0,
+                    strides: [0; K],
+                }],
+            };
+        }
+
+        let mut axes = (0..shape.len())
+            .filter(|&i| shape[i] != 1)
+            .map(|i| Loop {
+                extent: shape[i],
+                strides: layouts.map(|l| l.strides()[i]),
+            })
+            .collect::<Vec<_>>();
+        axes.sort_by_key(|l| Reverse(l.strides.map(|s| s.unsigned_abs())));
+
+        let mut loops = Vec::<Loop<K>>::with_capacity(axes.len());
+        for inner in axes {
+            match loops.last_mut() {
+                Some(outer)
+                    if (0..K)
+                        .all(|k| outer.strides[k] == inner.strides[k] * inner.extent as isize) =>
+                {
+                    outer.extent *= inner.extent;
+                    outer.strides = inner.strides;
+                }
+                _ => loops.push(inner),
+            }
+        }
+        Self { offsets, loops }
+    }
+}
+
+impl<const N: usize> ArrayLayout<N> {
+    /// 生成从 `src` 拷贝到 `self` 的循环嵌套，步长的第 0 项属于 `self`，第 1 项属于 `src`。
+    ///
+    /// ```rust
+    /// # use ndarray_layout::{ArrayLayout, Endian, Loop};
+    /// let dst = ArrayLayout::<4>::new_contiguous(&[2, 3, 4], Endian::BigEndian, 1);
+    /// let src = ArrayLayout::<4>::new(&[2, 3, 4], &[1, 8, 2], 0);
+    /// let plan = dst.copy_plan(&src).unwrap();
+    /// assert_eq!(
+    ///     plan.loops,
+    ///     [
+    ///         Loop { extent: 2, strides: [12, 1] },
+    ///         Loop { extent: 12, strides: [1, 2] },
+    ///     ]
+    /// );
+    /// ```
+    pub fn copy_plan<const M: usize>(
+        &self,
+        src: &ArrayLayout<M>,
+    ) -> Result<LoopNest<2>, ShapeMismatch> {
+        if self.shape() != src.shape() {
+            return Err(ShapeMismatch {
+                left: self.shape().to_vec(),
+                right: src.shape().to_vec(),
+            });
+        }
+        let src = ArrayLayout::<N>::new(src.shape(), src.strides(), src.offset());
+        Ok(LoopNest::new([self, &src]))
+    }
+
+    /// 寻找在 `self` 和 `src` 中都紧密排布的最大最内层数据块，
+    /// 返回其元素数量和需要遍历的外层布局，外层布局的每个偏移是一次内存拷贝的起点。
+    ///
+    /// ```rust
+    /// # use ndarray_layout::{ArrayLayout, Endian};
+    /// let dst = ArrayLayout::<4>::new_contiguous(&[2, 3, 4], Endian::BigEndian, 4);
+    /// let src = ArrayLayout::<4>::new(&[2, 3, 4], &[64, 16, 4], 8);
+    /// let chunk = dst.memcpy_chunk(&src, 4).unwrap();
+    /// assert_eq!(chunk.elements, 12);
+    /// assert_eq!(chunk.dst.shape(), &[2]);
+    /// assert_eq!(chunk.dst.strides(), &[48]);
+    /// assert_eq!(chunk.src.strides(), &[64]);
+    /// assert_eq!(chunk.src.offset(), 8);
+    /// ```
+    pub fn memcpy_chunk<const M: usize>(
+        &self,
+        src: &ArrayLayout<M>,
+        element_size: usize,
+    ) -> Result<MemcpyChunk<N>, ShapeMismatch> {
+        let LoopNest { offsets, mut loops } = self.copy_plan(src)?;
+        let unit = element_size as isize;
+        let elements = match loops.last() {
+            Some(&Loop { extent, strides }) if strides == [unit; 2] => {
+                loops.pop();
+                extent
+            }
+            _ if self.is_empty() => {
+                loops.clear();
+                0
+            }
+            _ => 1,
+        };
+
+        let shape = loops.iter().map(|l| l.extent).collect::<Vec<_>>();
+        let strides = |k: usize| loops.iter().map(|l| l.strides[k]).collect::<Vec<_>>();
+        Ok(MemcpyChunk {
+            elements,
+            dst: ArrayLayout::new(&shape, &strides(0), offsets[0]),
+            src: ArrayLayout::new(&shape, &strides(1), offsets[1]),
+        })
+    }
+
+    /// 为从 `self` 到 `dst` 的非原位转置推荐二维分块大小。
+    ///
+    /// 分块位于在两个张量中步长最小的两个阶上，使源和目标各一个分块能同时放入 `cache_bytes` 字节的缓存。
+    /// 两个张量步长最小的阶相同时不需要分块，返回 `None`。
+    ///
+    /// ```rust
+    /// # use ndarray_layout::{ArrayLayout, Endian, TransposeTiles};
+    /// let src = ArrayLayout::<2>::new_contiguous(&[1024, 1024], Endian::BigEndian, 4);
+    /// let dst = ArrayLayout::<2>::new_contiguous(&[1024, 1024], Endian::LittleEndian, 4);
+    /// assert_eq!(
+    ///     src.transpose_tiles(&dst, 32 << 10, 4),
+    ///     Some(TransposeTiles {
+    ///         axes: [1, 0],
+    ///         tile: [64, 64],
+    ///     })
+    /// );
+    /// assert_eq!(src.transpose_tiles(&src, 32 << 10, 4), None);
+    /// ```
+    pub fn transpose_tiles<const M: usize>(
+        &self,
+        dst: &ArrayLayout<M>,
+        cache_bytes: usize,
+        element_size: usize,
+    ) -> Option<TransposeTiles> {
+        let shape = self.shape();
+        assert_eq!(shape, dst.shape(), "layouts must have the same shape");
+        let fastest = |strides: &[isize]| {
+            (0..shape.len())
+                .filter(|&i| shape[i] > 1)
+                .min_by_key(|&i| strides[i].unsigned_abs())
+        };
+        let a = fastest(self.strides())?;
+        let b = fastest(dst.strides())?;
+        if a == b {
+            return None;
+        }
+
+        // 源和目标各一个分块
+        let capacity = (cache_bytes / element_size / 2).max(1);
+        let side = 1 << (capacity.ilog2() / 2);
+        let ta = side.min(shape[a]);
+        let tb = (capacity / ta).min(shape[b]);
+        Some(TransposeTiles {
+            axes: [a, b],
+            tile: [ta, tb],
+        })
+    }
+}
@@ -0,0 +1,105 @@
+//! 高阶布局元信息的堆分配。
+//!
+//! 启用 `pool` 特性时，每个线程缓存一定数量已释放的元信息内存块并在下次分配相同阶数时复用，
+//! 以减少大量创建和销毁临时布局时的分配器压力。
+
+use std::{
+    alloc::{alloc, dealloc, Layout},
+    ptr::NonNull,
+};
+
+#[inline]
+fn layout(ndim: usize) -> Layout {
+    Layout::array::<usize>(1 + ndim * 2).unwrap()
+}
+
+#[cfg(not(feature = "pool"))]
+#[inline]
+pub(crate) fn allocate(ndim: usize) -> NonNull<usize> {
+    let ptr = unsafe { alloc(layout(ndim)) };
+    NonNull::new(ptr.cast()).unwrap_or_else(|| std::alloc::handle_alloc_error(layout(ndim)))
+}
+
+#[cfg(not(feature = "pool"))]
+#[inline]
+pub(crate) unsafe fn release(ptr: NonNull<usize>, ndim: usize) {
+    dealloc(ptr.cast().as_ptr(), layout(ndim))
+}
+
+#[cfg(feature = "pool")]
+mod cache {
+    use super::*;
+    use std::cell::RefCell;
+
+    /// 缓存的最大阶数。
+    const MAX_NDIM: usize = 16;
+    /// 每种阶数缓存的最大块数。
+    const MAX_BLOCKS: usize = 64;
+
+    struct Pool([Vec<NonNull<usize>>; MAX_NDIM + 1]);
+
+    impl Drop for Pool {
+        fn drop(&mut self) {
+            for (ndim, blocks) in self.0.iter_mut().enumerate() {
+                for ptr in blocks.drain(..) {
+                    unsafe { dealloc(ptr.cast().as_ptr(), layout(ndim)) }
+                }
+            }
+        }
+    }
+
+    thread_local! {
+        static POOL: RefCell<Pool> = RefCell::new(Pool(std::array::from_fn(|_| Vec::new())));
+    }
+
+    #[inline]
+    pub(crate) fn allocate(ndim: usize) -> NonNull<usize> {
+        if ndim <= MAX_NDIM {
+            let cached = POOL
+                .try_with(|pool| pool.borrow_mut().0[ndim].pop())
+                .ok()
+                .flatten();
+            if let Some(ptr) = cached {
+                return ptr;
+            }
+        }
+        let ptr = unsafe { alloc(layout(ndim)) };
+        NonNull::new(ptr.cast()).unwrap_or_else(|| std::alloc::handle_alloc_error(layout(ndim)))
+    }
+
+    #[inline]
+    pub(crate) unsafe fn release(ptr: NonNull<usize>, ndim: usize) {
+        if ndim <= MAX_NDIM {
+            let cached = POOL.try_with(|pool| {
+                let blocks = &mut pool.borrow_mut().0[ndim];
+                let keep = blocks.len() < MAX_BLOCKS;
+                if keep {
+                    blocks.push(ptr)
+                }
+                keep
+            });
+            if let Ok(true) = cached {
+                return;
+            }
+        }
+        dealloc(ptr.cast().as_ptr(), layout(ndim))
+    }
+}
+
+#[cfg(feature = "pool")]
+pub(crate) use cache::{allocate, release};
+
+#[cfg(feature = "pool")]
+#[test]
+fn test() {
+    let a = allocate(3);
+    unsafe { release(a, 3) };
+    let b = allocate(3);
+    assert_eq!(a, b);
+    let c = allocate(3);
+    assert_ne!(b, c);
+    unsafe {
+        release(b, 3);
+        release(c, 3);
+    }
+}
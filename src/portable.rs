@@ -0,0 +1,127 @@
+use crate::ArrayLayout;
+
+/// 以固定宽度整数表示的布局，与平台的指针宽度无关。
+///
+/// 在 64 位平台上保存的布局可以在 32 位平台上以检查溢出的方式恢复。
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct PortableLayout {
+    /// 偏移。
+    pub offset: i64,
+    /// 形状。
+    pub shape: Vec<u64>,
+    /// 步长。
+    pub strides: Vec<i64>,
+}
+
+/// 从 [`PortableLayout`] 恢复布局的错误。
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum PortableError {
+    /// 形状和步长的长度不同。
+    LengthMismatch {
+        /// 形状的长度。
+        shape: usize,
+        /// 步长的长度。
+        strides: usize,
+    },
+    /// 偏移超出当前平台 `isize` 的范围。
+    OffsetOverflow(i64),
+    /// 某阶的长度超出当前平台 `usize` 的范围。
+    DimOverflow {
+        /// 阶号。
+        axis: usize,
+        /// 该阶的长度。
+        dim: u64,
+    },
+    /// 某阶的步长超出当前平台 `isize` 的范围。
+    StrideOverflow {
+        /// 阶号。
+        axis: usize,
+        /// 该阶的步长。
+        stride: i64,
+    },
+}
+
+impl<const N: usize> ArrayLayout<N> {
+    /// 转换为固定宽度整数表示的布局。
+    ///
+    /// ```rust
+    /// # use ndarray_layout::{ArrayLayout, PortableLayout};
+    /// let layout = ArrayLayout::<3>::new(&[2, 3], &[12, -4], 8);
+    /// let portable = layout.to_portable();
+    /// assert_eq!(
+    ///     portable,
+    ///     PortableLayout { offset: 8, shape: vec![2, 3], strides: vec![12, -4] }
+    /// );
+    /// assert_eq!(ArrayLayout::<3>::from_portable(&portable).unwrap(), layout);
+    /// ```
+    pub fn to_portable(&self) -> PortableLayout {
+        let content = self.content();
+        PortableLayout {
+            offset: content.offset() as _,
+            shape: content.shape().iter().map(|&d| d as _).collect(),
+            strides: content.strides().iter().map(|&s| s as _).collect(),
+        }
+    }
+
+    /// 从固定宽度整数表示的布局恢复，任何整数超出当前平台的表示范围时返回错误。
+    ///
+    /// ```rust
+    /// # use ndarray_layout::{ArrayLayout, PortableError, PortableLayout};
+    /// let portable = PortableLayout { offset: 0, shape: vec![2, 3], strides: vec![4] };
+    /// assert_eq!(
+    ///     ArrayLayout::<3>::from_portable(&portable),
+    ///     Err(PortableError::LengthMismatch { shape: 2, strides: 1 })
+    /// );
+    /// ```
+    pub fn from_portable(portable: &PortableLayout) -> Result<Self, PortableError> {
+        let PortableLayout {
+            offset,
+            ref shape,
+            ref strides,
+        } = *portable;
+        if shape.len() != strides.len() {
+            return Err(PortableError::LengthMismatch {
+                shape: shape.len(),
+                strides: strides.len(),
+            });
+        }
+
+        let mut ans = Self::with_ndim(shape.len());
+        let mut content = ans.content_mut();
+        content.set_offset(
+            offset
+                .try_into()
+                .map_err(|_| PortableError::OffsetOverflow(offset))?,
+        );
+        for (axis, (&dim, &stride)) in shape.iter().zip(strides).enumerate() {
+            content.set_shape(
+                axis,
+                dim.try_into()
+                    .map_err(|_| PortableError::DimOverflow { axis, dim })?,
+            );
+            content.set_stride(
+                axis,
+                stride
+                    .try_into()
+                    .map_err(|_| PortableError::StrideOverflow { axis, stride })?,
+            );
+        }
+        Ok(ans)
+    }
+}
+
+impl<const N: usize> From<&ArrayLayout<N>> for PortableLayout {
+    #[inline]
+    fn from(layout: &ArrayLayout<N>) -> Self {
+        layout.to_portable()
+    }
+}
+
+impl<const N: usize> TryFrom<&PortableLayout> for ArrayLayout<N> {
+    type Error = PortableError;
+
+    #[inline]
+    fn try_from(portable: &PortableLayout) -> Result<Self, Self::Error> {
+        Self::from_portable(portable)
+    }
+}
@@ -0,0 +1,247 @@
+use crate::{ArrayLayout, StaticLayout, TypedLayout};
+use std::{iter::zip, ops::RangeInclusive};
+
+/// 只读访问布局元信息的抽象。
+///
+/// 泛型代码可以接受任意内联容量的 [`ArrayLayout`] 或自定义的布局类型，
+/// 而不必对内联容量单态化。
+///
+/// ```rust
+/// # use ndarray_layout::{ArrayLayout, Endian, LayoutRead, StaticLayout};
+/// fn last_offset(layout: &impl LayoutRead) -> isize {
+///     let indices = layout.shape().iter().map(|&d| d - 1).collect::<Vec<_>>();
+///     layout.offset_of(&indices)
+/// }
+///
+/// let a = ArrayLayout::<2>::new(&[2, 3, 4], &[12, 4, 1], 0);
+/// let b = ArrayLayout::<4>::new(&[2, 3, 4], &[12, 4, 1], 0);
+/// let c = StaticLayout::new_contiguous([2, 3, 4], Endian::BigEndian, 1);
+/// assert_eq!(last_offset(&a), 23);
+/// assert_eq!(last_offset(&b), 23);
+/// assert_eq!(last_offset(&c), 23);
+/// assert_eq!(c.to_layout::<2>(), a);
+/// ```
+pub trait LayoutRead {
+    /// 阶数。
+    #[inline]
+    fn ndim(&self) -> usize {
+        self.shape().len()
+    }
+
+    /// 形状。
+    fn shape(&self) -> &[usize];
+
+    /// 步长。
+    fn strides(&self) -> &[isize];
+
+    /// 偏移。
+    fn offset(&self) -> isize;
+
+    /// 元素数量，即形状的乘积，同 [`ArrayLayout::num_elements`]。
+    ///
+    /// 存在长度为 0 的阶时为 0，否则乘积溢出 `usize` 时 panic。
+    ///
+    /// ```rust
+    /// # use ndarray_layout::{LayoutRead, StaticLayout};
+    /// let layout = StaticLayout { shape: [usize::MAX, 2, 0], strides: [0; 3], offset: 0 };
+    /// assert_eq!(LayoutRead::num_elements(&layout), 0);
+    /// let layout = layout.to_layout::<4>();
+    /// assert_eq!(LayoutRead::num_elements(&layout), layout.num_elements());
+    /// ```
+    fn num_elements(&self) -> usize {
+        let shape = self.shape();
+        if shape.contains(&0) {
+            return 0;
+        }
+        shape
+            .iter()
+            .try_fold(1usize, |acc, &d| acc.checked_mul(d))
+            .expect("number of elements overflows usize")
+    }
+
+    /// 判断是否存在长度为 0 的阶。
+    #[inline]
+    fn is_empty(&self) -> bool {
+        self.shape().contains(&0)
+    }
+
+    /// 计算多维索引对应的偏移，索引的数量与阶数不同或任何索引越界时 panic。
+    fn offset_of(&self, indices: &[usize]) -> isize {
+        let shape = self.shape();
+        assert_eq!(
+            indices.len(),
+            shape.len(),
+            "indices and shape must have the same length"
+        );
+        zip(indices, zip(shape, self.strides())).enumerate().fold(
+            self.offset(),
+            |acc, (axis, (&i, (&d, &s)))| {
+                assert!(i < d, "index {i} out of range {d} at axis {axis}");
+                acc + i as isize * s
+            },
+        )
+    }
+
+    /// 数据访问的偏移范围，同 [`ArrayLayout::data_range`]。
+    fn data_range(&self) -> RangeInclusive<isize> {
+        let mut start = self.offset();
+        let mut end = self.offset();
+        for (&d, &s) in zip(self.shape(), self.strides()) {
+            let span = (d as isize - 1) * s;
+            if span < 0 {
+                start += span
+            } else {
+                end += span
+            }
+        }
+        start..=end
+    }
+
+    /// 转换为指定内联容量的 [`ArrayLayout`]，以进行各种变换。
+    #[inline]
+    fn to_layout<const N: usize>(&self) -> ArrayLayout<N> {
+        ArrayLayout::new(self.shape(), self.strides(), self.offset())
+    }
+
+    /// 切片变换，结果为指定内联容量的 [`ArrayLayout`]，同 [`ArrayLayout::slice`]。
+    ///
+    /// ```rust
+    /// # use ndarray_layout::{Endian, LayoutRead, StaticLayout};
+    /// let layout = StaticLayout::new_contiguous([2, 3, 4], Endian::BigEndian, 1);
+    /// let sliced = layout.slice_to::<3>(2, 3, -1, 2);
+    /// assert_eq!(sliced.shape(), &[2, 3, 2]);
+    /// assert_eq!(sliced.strides(), &[12, 4, -1]);
+    /// assert_eq!(sliced.offset(), 3);
+    ///
+    /// let indexed = layout.index_to::<2>(1, 2);
+    /// assert_eq!(indexed.shape(), &[2, 4]);
+    /// assert_eq!(indexed.offset(), 8);
+    ///
+    /// let transposed = layout.transpose_to::<3>(&[2, 0]);
+    /// assert_eq!(transposed.shape(), &[4, 3, 2]);
+    /// assert_eq!(transposed.strides(), &[1, 4, 12]);
+    /// ```
+    #[inline]
+    fn slice_to<const M: usize>(
+        &self,
+        axis: usize,
+        start: usize,
+        step: isize,
+        len: usize,
+    ) -> ArrayLayout<M> {
+        self.to_layout::<M>().slice(axis, start, step, len)
+    }
+
+    /// 索引变换，结果为指定内联容量的 [`ArrayLayout`]，同 [`ArrayLayout::index`]。
+    #[inline]
+    fn index_to<const M: usize>(&self, axis: usize, index: usize) -> ArrayLayout<M> {
+        self.to_layout::<M>().index(axis, index)
+    }
+
+    /// 转置变换，结果为指定内联容量的 [`ArrayLayout`]，同 [`ArrayLayout::transpose`]。
+    #[inline]
+    fn transpose_to<const M: usize>(&self, perm: &[usize]) -> ArrayLayout<M> {
+        let mut ans = self.to_layout::<M>();
+        ans.transpose_inplace(perm);
+        ans
+    }
+}
+
+impl<const N: usize> LayoutRead for ArrayLayout<N> {
+    #[inline]
+    fn ndim(&self) -> usize {
+        self.ndim()
+    }
+
+    #[inline]
+    fn shape(&self) -> &[usize] {
+        self.shape()
+    }
+
+    #[inline]
+    fn strides(&self) -> &[isize] {
+        self.strides()
+    }
+
+    #[inline]
+    fn offset(&self) -> isize {
+        self.offset()
+    }
+
+    #[inline]
+    fn num_elements(&self) -> usize {
+        self.num_elements()
+    }
+
+    #[inline]
+    fn is_empty(&self) -> bool {
+        self.is_empty()
+    }
+
+    #[inline]
+    fn offset_of(&self, indices: &[usize]) -> isize {
+        self.offset_of(indices)
+    }
+
+    #[inline]
+    fn data_range(&self) -> RangeInclusive<isize> {
+        self.data_range()
+    }
+}
+
+impl<const D: usize> LayoutRead for StaticLayout<D> {
+    #[inline]
+    fn shape(&self) -> &[usize] {
+        &self.shape
+    }
+
+    #[inline]
+    fn strides(&self) -> &[isize] {
+        &self.strides
+    }
+
+    #[inline]
+    fn offset(&self) -> isize {
+        self.offset
+    }
+}
+
+/// 以字节为单位访问。
+impl<const N: usize> LayoutRead for TypedLayout<N> {
+    #[inline]
+    fn shape(&self) -> &[usize] {
+        self.shape()
+    }
+
+    #[inline]
+    fn strides(&self) -> &[isize] {
+        self.byte_strides()
+    }
+
+    #[inline]
+    fn offset(&self) -> isize {
+        self.byte_offset()
+    }
+}
+
+impl<T: LayoutRead + ?Sized> LayoutRead for &T {
+    #[inline]
+    fn ndim(&self) -> usize {
+        (**self).ndim()
+    }
+
+    #[inline]
+    fn shape(&self) -> &[usize] {
+        (**self).shape()
+    }
+
+    #[inline]
+    fn strides(&self) -> &[isize] {
+        (**self).strides()
+    }
+
+    #[inline]
+    fn offset(&self) -> isize {
+        (**self).offset()
+    }
+}
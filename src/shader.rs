@@ -0,0 +1,181 @@
+use crate::ArrayLayout;
+use std::fmt::Write;
+
+/// 着色器语言。
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum ShaderLang {
+    /// WebGPU 着色语言。
+    Wgsl,
+    /// OpenGL/Vulkan 着色语言。
+    Glsl,
+}
+
+/// 缓冲区的内存排布规则。
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum UniformRules {
+    /// uniform 缓冲区使用的规则，数组元素对齐到 16 字节，形状和步长每 4 个打包为一个向量。
+    Std140,
+    /// storage 缓冲区使用的规则，形状和步长为紧密排列的标量数组。
+    Std430,
+}
+
+/// 在着色器中以固定的最大阶数描述布局的结构体。
+///
+/// 结构体依次包含 `ndim: u32`、`offset: i32`、`shape` 和 `strides`，偏移和步长以元素为单位，
+/// 超出 `ndim` 的阶长度为 1、步长为 0。
+/// 按 [`Std140`](UniformRules::Std140) 规则时第 `i` 阶的长度为 `shape[i / 4][i % 4]`。
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct ShaderLayout {
+    /// 最大阶数。
+    pub max_rank: usize,
+    /// 内存排布规则。
+    pub rules: UniformRules,
+}
+
+impl ShaderLayout {
+    /// 形状数组的字节偏移。
+    #[inline]
+    fn shape_offset(&self) -> usize {
+        match self.rules {
+            UniformRules::Std140 => 16,
+            UniformRules::Std430 => 8,
+        }
+    }
+
+    /// 形状或步长数组的字节数。
+    #[inline]
+    fn array_size(&self) -> usize {
+        match self.rules {
+            UniformRules::Std140 => self.max_rank.div_ceil(4) * 16,
+            UniformRules::Std430 => self.max_rank * 4,
+        }
+    }
+
+    /// 结构体的字节数。
+    #[inline]
+    pub fn size(&self) -> usize {
+        self.shape_offset() + self.array_size() * 2
+    }
+
+    /// 生成着色器中的结构体定义。
+    ///
+    /// ```rust
+    /// # use ndarray_layout::{ShaderLang, ShaderLayout, UniformRules};
+    /// let shader = ShaderLayout { max_rank: 6, rules: UniformRules::Std140 };
+    /// assert_eq!(
+    ///     shader.struct_definition(ShaderLang::Wgsl, "Layout"),
+    ///     "struct Layout {
+    ///     ndim: u32,
+    ///     offset: i32,
+    ///     shape: array<vec4<u32>, 2>,
+    ///     strides: array<vec4<i32>, 2>,
+    /// }
+    /// "
+    /// );
+    ///
+    /// let shader = ShaderLayout { max_rank: 6, rules: UniformRules::Std430 };
+    /// assert_eq!(
+    ///     shader.struct_definition(ShaderLang::Glsl, "Layout"),
+    ///     "struct Layout {
+    ///     uint ndim;
+    ///     int offset;
+    ///     uint shape[6];
+    ///     int strides[6];
+    /// };
+    /// "
+    /// );
+    /// ```
+    pub fn struct_definition(&self, lang: ShaderLang, name: &str) -> String {
+        let (shape, strides, len) = match self.rules {
+            UniformRules::Std140 => (
+                ["vec4<u32>", "uvec4"],
+                ["vec4<i32>", "ivec4"],
+                self.max_rank.div_ceil(4),
+            ),
+            UniformRules::Std430 => (["u32", "uint"], ["i32", "int"], self.max_rank),
+        };
+        let mut ans = String::new();
+        match lang {
+            ShaderLang::Wgsl => {
+                let _ = writeln!(ans, "struct {name} {{");
+                let _ = writeln!(ans, "    ndim: u32,");
+                let _ = writeln!(ans, "    offset: i32,");
+                let _ = writeln!(ans, "    shape: array<{}, {len}>,", shape[0]);
+                let _ = writeln!(ans, "    strides: array<{}, {len}>,", strides[0]);
+                let _ = writeln!(ans, "}}");
+            }
+            ShaderLang::Glsl => {
+                let _ = writeln!(ans, "struct {name} {{");
+                let _ = writeln!(ans, "    uint ndim;");
+                let _ = writeln!(ans, "    int offset;");
+                let _ = writeln!(ans, "    {} shape[{len}];", shape[1]);
+                let _ = writeln!(ans, "    {} strides[{len}];", strides[1]);
+                let _ = writeln!(ans, "}};");
+            }
+        }
+        ans
+    }
+
+    /// 按小端序将布局打包为结构体的字节。
+    ///
+    /// `element_size` 为 0、阶数超过 `max_rank`、偏移或步长不是 `element_size` 的整数倍，
+    /// 或者数值超出 32 位整数的范围时返回 `None`。
+    ///
+    /// ```rust
+    /// # use ndarray_layout::{ArrayLayout, ShaderLayout, UniformRules};
+    /// let layout = ArrayLayout::<2>::new(&[2, 3], &[12, 4], 8);
+    /// let shader = ShaderLayout { max_rank: 2, rules: UniformRules::Std430 };
+    /// let bytes = shader.pack(&layout, 4).unwrap();
+    /// assert_eq!(bytes.len(), shader.size());
+    /// assert_eq!(
+    ///     bytes.chunks(4).map(|b| i32::from_le_bytes(b.try_into().unwrap())).collect::<Vec<_>>(),
+    ///     [2, 2, 2, 3, 3, 1]
+    /// );
+    ///
+    /// let shader = ShaderLayout { max_rank: 2, rules: UniformRules::Std140 };
+    /// assert_eq!(shader.pack(&layout, 4).unwrap().len(), 48);
+    /// assert_eq!(shader.pack(&layout, 8), None);
+    /// assert_eq!(shader.pack(&layout, 0), None);
+    /// ```
+    pub fn pack<const N: usize>(
+        &self,
+        layout: &ArrayLayout<N>,
+        element_size: usize,
+    ) -> Option<Vec<u8>> {
+        let ndim = layout.ndim();
+        if ndim > self.max_rank {
+            return None;
+        }
+        let size = isize::try_from(element_size)
+            .ok()
+            .filter(|&size| size > 0)?;
+        let elements = |s: isize| {
+            if s % size == 0 {
+                i32::try_from(s / size).ok()
+            } else {
+                None
+            }
+        };
+
+        let mut ans = vec![0u8; self.size()];
+        ans[0..4].copy_from_slice(&u32::try_from(ndim).ok()?.to_le_bytes());
+        ans[4..8].copy_from_slice(&elements(layout.offset())?.to_le_bytes());
+        let shape_offset = self.shape_offset();
+        let strides_offset = shape_offset + self.array_size();
+        for i in 0..self.max_rank {
+            let (d, s) = if i < ndim {
+                (
+                    u32::try_from(layout.shape()[i]).ok()?,
+                    elements(layout.strides()[i])?,
+                )
+            } else {
+                (1, 0)
+            };
+            let d_ = shape_offset + i * 4;
+            let s_ = strides_offset + i * 4;
+            ans[d_..d_ + 4].copy_from_slice(&d.to_le_bytes());
+            ans[s_..s_ + 4].copy_from_slice(&s.to_le_bytes());
+        }
+        Some(ans)
+    }
+}
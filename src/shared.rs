@@ -0,0 +1,91 @@
+use crate::{ArrayLayout, LayoutRead};
+use std::{ops::Deref, sync::Arc};
+
+/// 共享元信息存储的布局。
+///
+/// 复制只增加引用计数，修改时才复制元信息，适合在图表示中大量持有高阶布局。
+/// 通过 [`Deref`] 可以直接调用 [`ArrayLayout`] 的所有只读方法和变换。
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct SharedLayout<const N: usize = 2>(Arc<ArrayLayout<N>>);
+
+impl<const N: usize> SharedLayout<N> {
+    /// 共享一个布局。
+    #[inline]
+    pub fn new(layout: ArrayLayout<N>) -> Self {
+        Self(Arc::new(layout))
+    }
+
+    /// 判断两个共享布局是否引用同一份元信息。
+    ///
+    /// ```rust
+    /// # use ndarray_layout::{ArrayLayout, SharedLayout};
+    /// let a = SharedLayout::new(ArrayLayout::<2>::new(&[2, 3, 4], &[12, 4, 1], 0));
+    /// let mut b = a.clone();
+    /// assert!(a.ptr_eq(&b));
+    ///
+    /// b.update(|layout| layout.transpose(&[2, 0]));
+    /// assert!(!a.ptr_eq(&b));
+    /// assert_eq!(a.strides(), &[12, 4, 1]);
+    /// assert_eq!(b.strides(), &[1, 4, 12]);
+    /// ```
+    #[inline]
+    pub fn ptr_eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+
+    /// 获取布局的可变引用，元信息被共享时先复制一份。
+    #[inline]
+    pub fn make_mut(&mut self) -> &mut ArrayLayout<N> {
+        Arc::make_mut(&mut self.0)
+    }
+
+    /// 以变换的结果替换当前布局，不影响其他共享者。
+    #[inline]
+    pub fn update(&mut self, f: impl FnOnce(&ArrayLayout<N>) -> ArrayLayout<N>) {
+        self.0 = Arc::new(f(&self.0))
+    }
+
+    /// 取出布局，元信息被共享时复制一份。
+    #[inline]
+    pub fn into_layout(self) -> ArrayLayout<N> {
+        Arc::try_unwrap(self.0).unwrap_or_else(|arc| (*arc).clone())
+    }
+}
+
+impl<const N: usize> Deref for SharedLayout<N> {
+    type Target = ArrayLayout<N>;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<const N: usize> From<ArrayLayout<N>> for SharedLayout<N> {
+    #[inline]
+    fn from(layout: ArrayLayout<N>) -> Self {
+        Self::new(layout)
+    }
+}
+
+impl<const N: usize> LayoutRead for SharedLayout<N> {
+    #[inline]
+    fn ndim(&self) -> usize {
+        self.0.ndim()
+    }
+
+    #[inline]
+    fn shape(&self) -> &[usize] {
+        self.0.shape()
+    }
+
+    #[inline]
+    fn strides(&self) -> &[isize] {
+        self.0.strides()
+    }
+
+    #[inline]
+    fn offset(&self) -> isize {
+        self.0.offset()
+    }
+}
@@ -0,0 +1,86 @@
+use crate::ArrayLayout;
+
+/// 基于异或的位重排。
+///
+/// 将字节偏移中 `[base + shift, base + shift + bits)` 的位异或到 `[base, base + bits)` 上，
+/// 常用于避免 GPU 共享内存的存储体冲突。`shift >= bits` 时重排是偏移上的双射。
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct Swizzle {
+    /// 参与异或的位数。
+    pub bits: u32,
+    /// 被修改的最低位，低于此位的偏移保持不变。
+    pub base: u32,
+    /// 异或来源相对于被修改位的距离。
+    pub shift: u32,
+}
+
+impl Swizzle {
+    /// 创建位重排。
+    ///
+    /// # Panics
+    ///
+    /// `shift < bits` 时重排不是双射，panic。
+    pub const fn new(bits: u32, base: u32, shift: u32) -> Self {
+        assert!(shift >= bits, "swizzle shift must not be less than bits");
+        Self { bits, base, shift }
+    }
+
+    /// 对字节偏移应用位重排。
+    ///
+    /// ```rust
+    /// # use ndarray_layout::Swizzle;
+    /// let swizzle = Swizzle::new(3, 4, 3);
+    /// assert_eq!(swizzle.apply(0b0_0000), 0b0_0000);
+    /// assert_eq!(swizzle.apply(0b10_1000_0000), 0b10_1101_0000);
+    /// assert_eq!(swizzle.apply(swizzle.apply(1234)), 1234);
+    /// ```
+    #[inline]
+    pub const fn apply(&self, offset: isize) -> isize {
+        let mask = ((1 << self.bits) - 1) << self.base;
+        offset ^ ((offset >> self.shift) & mask)
+    }
+}
+
+/// 在仿射映射之后附加位重排的布局。
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct SwizzledLayout<const N: usize = 2> {
+    layout: ArrayLayout<N>,
+    swizzle: Swizzle,
+}
+
+impl<const N: usize> SwizzledLayout<N> {
+    /// 为布局附加位重排。
+    ///
+    /// ```rust
+    /// # use ndarray_layout::{ArrayLayout, Endian, Swizzle, SwizzledLayout};
+    /// // 8 行 32 列 f32，每行 128 字节，以 16 字节为单位按行号交错
+    /// let layout = ArrayLayout::<2>::new_contiguous(&[8, 32], Endian::BigEndian, 4);
+    /// let swizzled = SwizzledLayout::new(layout, Swizzle::new(3, 4, 3));
+    /// assert_eq!(swizzled.offset_of(&[0, 5]), 20);
+    /// assert_eq!(swizzled.offset_of(&[1, 0]), 144);
+    /// assert_eq!(swizzled.offset_of(&[1, 4]), 128);
+    /// assert_eq!(swizzled.offset_of(&[3, 9]), 3 * 128 + (2 ^ 3) * 16 + 4);
+    /// ```
+    #[inline]
+    pub fn new(layout: ArrayLayout<N>, swizzle: Swizzle) -> Self {
+        Self { layout, swizzle }
+    }
+
+    /// 位重排之前的仿射布局。
+    #[inline]
+    pub fn layout(&self) -> &ArrayLayout<N> {
+        &self.layout
+    }
+
+    /// 附加的位重排。
+    #[inline]
+    pub fn swizzle(&self) -> Swizzle {
+        self.swizzle
+    }
+
+    /// 计算多维索引对应的偏移，含义同 [`ArrayLayout::offset_of`]。
+    #[inline]
+    pub fn offset_of(&self, indices: &[usize]) -> isize {
+        self.swizzle.apply(self.layout.offset_of(indices))
+    }
+}
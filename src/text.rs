@@ -0,0 +1,106 @@
+use crate::ArrayLayout;
+use std::{fmt, str::FromStr};
+
+/// 布局文本解析错误。
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum ParseLayoutError {
+    /// 缺少期望的内容。
+    Expected(&'static str),
+    /// 无法解析的整数。
+    InvalidInteger(String),
+    /// 形状和步长的长度不同。
+    LengthMismatch {
+        /// 形状的长度。
+        shape: usize,
+        /// 步长的长度。
+        strides: usize,
+    },
+}
+
+impl<const N: usize> fmt::Display for ArrayLayout<N> {
+    /// 以 `shape=[2,3,4] strides=[12,4,1] offset=0` 的形式显示布局。
+    ///
+    /// ```rust
+    /// # use ndarray_layout::ArrayLayout;
+    /// let layout = ArrayLayout::<3>::new(&[2, 3, 4], &[12, -4, 1], 8);
+    /// assert_eq!(layout.to_string(), "shape=[2,3,4] strides=[12,-4,1] offset=8");
+    /// assert!(layout.to_string().parse::<ArrayLayout<3>>().unwrap() == layout);
+    /// ```
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fn list<T: fmt::Display>(f: &mut fmt::Formatter<'_>, items: &[T]) -> fmt::Result {
+            write!(f, "[")?;
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    write!(f, ",")?;
+                }
+                write!(f, "{item}")?;
+            }
+            write!(f, "]")
+        }
+
+        write!(f, "shape=")?;
+        list(f, self.shape())?;
+        write!(f, " strides=")?;
+        list(f, self.strides())?;
+        write!(f, " offset={}", self.offset())
+    }
+}
+
+impl<const N: usize> FromStr for ArrayLayout<N> {
+    type Err = ParseLayoutError;
+
+    /// 解析 [`Display`](fmt::Display) 产生的文本，允许多余的空白。
+    ///
+    /// ```rust
+    /// # use ndarray_layout::{ArrayLayout, ParseLayoutError};
+    /// let layout = " shape = [2, 3] strides=[3, 1]  offset=0 ".parse::<ArrayLayout<2>>().unwrap();
+    /// assert_eq!(layout.shape(), &[2, 3]);
+    /// assert_eq!(layout.strides(), &[3, 1]);
+    /// assert_eq!(layout.offset(), 0);
+    ///
+    /// assert_eq!(
+    ///     "shape=[2] strides=[] offset=0".parse::<ArrayLayout<2>>().err(),
+    ///     Some(ParseLayoutError::LengthMismatch { shape: 1, strides: 0 })
+    /// );
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut s = s.trim_start();
+        let shape = list(&mut s, "shape")?;
+        let strides = list(&mut s, "strides")?;
+        expect(&mut s, "offset")?;
+        expect(&mut s, "=")?;
+        let offset = integer(s.trim())?;
+        if shape.len() != strides.len() {
+            return Err(ParseLayoutError::LengthMismatch {
+                shape: shape.len(),
+                strides: strides.len(),
+            });
+        }
+        Ok(Self::new(&shape, &strides, offset))
+    }
+}
+
+fn expect(s: &mut &str, token: &'static str) -> Result<(), ParseLayoutError> {
+    *s = s
+        .strip_prefix(token)
+        .ok_or(ParseLayoutError::Expected(token))?
+        .trim_start();
+    Ok(())
+}
+
+fn list<T: FromStr>(s: &mut &str, key: &'static str) -> Result<Vec<T>, ParseLayoutError> {
+    expect(s, key)?;
+    expect(s, "=")?;
+    expect(s, "[")?;
+    let (items, rest) = s.split_once(']').ok_or(ParseLayoutError::Expected("]"))?;
+    *s = rest.trim_start();
+    if items.trim().is_empty() {
+        return Ok(Vec::new());
+    }
+    items.split(',').map(|item| integer(item.trim())).collect()
+}
+
+fn integer<T: FromStr>(s: &str) -> Result<T, ParseLayoutError> {
+    s.parse()
+        .map_err(|_| ParseLayoutError::InvalidInteger(s.into()))
+}
@@ -0,0 +1,52 @@
+﻿use crate::ArrayLayout;
+
+impl<const N: usize> ArrayLayout<N> {
+    /// 分组变换将单个阶大端分块为外层和长度为 `block` 的内层，并将内层移动到 `to` 处。
+    /// 例如 `NCHW` 的通道阶以 8 分组并移动到最后即得到 `NCHWc8`，`to` 是变换后张量中的位置。
+    ///
+    /// ```rust
+    /// # use ndarray_layout::{ArrayLayout, Endian};
+    /// let nchw = ArrayLayout::<5>::new_contiguous(&[2, 16, 3, 3], Endian::BigEndian, 1);
+    /// let nchwc8 = nchw.block(1, 8, 4);
+    /// assert_eq!(nchwc8.shape(), &[2, 2, 3, 3, 8]);
+    /// assert_eq!(nchwc8.strides(), &[144, 72, 3, 1, 9]);
+    /// assert_eq!(nchwc8.offset(), 0);
+    ///
+    /// let nchw_ = nchwc8.unblock(1, 4).unwrap();
+    /// assert!(nchw_ == nchw);
+    /// ```
+    pub fn block(&self, axis: usize, block: usize, to: usize) -> Self {
+        let d = self.shape()[axis];
+        assert!(
+            block > 0 && d.is_multiple_of(block),
+            "block {block} does not divide dim {d}"
+        );
+        self.tile_be(axis, &[d / block, block])
+            .move_axis(axis + 1, to)
+    }
+
+    /// 分组变换的逆变换，将内层 `inner` 移回外层 `outer` 之后并与之合并。
+    /// 无法合并时返回 `None`。
+    pub fn unblock(&self, outer: usize, inner: usize) -> Option<Self> {
+        assert_ne!(outer, inner);
+        if inner > outer {
+            self.move_axis(inner, outer + 1).merge(outer..outer + 2)
+        } else {
+            self.move_axis(inner, outer).merge(outer - 1..outer + 1)
+        }
+    }
+}
+
+#[test]
+fn test() {
+    use crate::Endian;
+
+    // OIhw -> OIhw16i16o
+    let oihw = ArrayLayout::<6>::new_contiguous(&[32, 16, 3, 3], Endian::BigEndian, 1);
+    let blocked = oihw.block(1, 16, 4).block(0, 16, 5);
+    assert_eq!(blocked.shape(), &[2, 1, 3, 3, 16, 16]);
+    assert_eq!(blocked.strides(), &[2304, 144, 3, 1, 9, 144]);
+
+    let oihw_ = blocked.unblock(0, 5).unwrap().unblock(1, 4).unwrap();
+    assert!(oihw_ == oihw);
+}
@@ -1,7 +1,8 @@
 ﻿use crate::ArrayLayout;
+use std::{error::Error, fmt};
 
 /// 索引变换参数。
-#[derive(Clone, PartialEq, Eq, Debug)]
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
 pub struct BroadcastArg {
     /// 广播的轴。
     pub axis: usize,
@@ -9,6 +10,44 @@ pub struct BroadcastArg {
     pub times: usize,
 }
 
+/// 广播错误。
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum BroadcastError {
+    /// 目标形状的阶数少于原形状。
+    RankMismatch {
+        /// 原形状的阶数。
+        ndim: usize,
+        /// 目标形状的阶数。
+        target: usize,
+    },
+    /// 某一阶的长度既不等于目标长度也不为 1。
+    ShapeMismatch {
+        /// 不兼容的阶，以目标形状计。
+        axis: usize,
+        /// 原长度。
+        dim: usize,
+        /// 目标长度。
+        target: usize,
+    },
+}
+
+impl fmt::Display for BroadcastError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Self::RankMismatch { ndim, target } => write!(
+                f,
+                "cannot broadcast {ndim} dims to {target}: expected target rank >= {ndim}"
+            ),
+            Self::ShapeMismatch { axis, dim, target } => write!(
+                f,
+                "cannot broadcast dim {dim} to {target} at axis {axis}: expected 1 or {target}"
+            ),
+        }
+    }
+}
+
+impl Error for BroadcastError {}
+
 impl<const N: usize> ArrayLayout<N> {
     /// 广播变换将指定的长度为 1 的阶扩增指定的倍数，并将其步长固定为 0。
     ///
@@ -26,12 +65,150 @@ impl<const N: usize> ArrayLayout<N> {
     /// 一次对多个阶进行广播变换。
     pub fn broadcast_many(&self, args: &[BroadcastArg]) -> Self {
         let mut ans = self.clone();
-        let mut content = ans.content_mut();
+        ans.broadcast_many_inplace(args);
+        ans
+    }
+
+    /// 原地进行广播变换，不构造新的布局。
+    ///
+    /// ```rust
+    /// # use ndarray_layout::ArrayLayout;
+    /// let mut layout = ArrayLayout::<3>::new(&[1, 5, 2], &[10, 2, 1], 0);
+    /// layout.broadcast_inplace(0, 10);
+    /// assert_eq!(layout.shape(), &[10, 5, 2]);
+    /// assert_eq!(layout.strides(), &[0, 2, 1]);
+    /// ```
+    #[inline]
+    pub fn broadcast_inplace(&mut self, axis: usize, times: usize) {
+        self.broadcast_many_inplace(&[BroadcastArg { axis, times }])
+    }
+
+    /// 原地对多个阶进行广播变换。
+    pub fn broadcast_many_inplace(&mut self, args: &[BroadcastArg]) {
+        let mut content = self.content_mut();
         for &BroadcastArg { axis, times } in args {
             assert!(content.shape()[axis] == 1 || content.strides()[axis] == 0);
             content.set_shape(axis, times);
             content.set_stride(axis, 0);
         }
+    }
+
+    /// 在布局前方补充指定长度、步长为 0 的新阶。
+    ///
+    /// ```rust
+    /// # use ndarray_layout::ArrayLayout;
+    /// let layout = ArrayLayout::<3>::new(&[3, 4], &[4, 1], 0).prepend_broadcast(&[2, 5]);
+    /// assert_eq!(layout.shape(), &[2, 5, 3, 4]);
+    /// assert_eq!(layout.strides(), &[0, 0, 4, 1]);
+    /// assert_eq!(layout.offset(), 0);
+    /// ```
+    pub fn prepend_broadcast(&self, dims: &[usize]) -> Self {
+        let content = self.content();
+        let shape = content.shape();
+        let strides = content.strides();
+        let prefix = dims.len();
+
+        let mut ans = Self::with_ndim(prefix + self.ndim);
+        let mut content = ans.content_mut();
+        content.set_offset(self.offset());
+        for (i, &d) in dims.iter().enumerate() {
+            content.set_shape(i, d);
+            content.set_stride(i, 0);
+        }
+        for (i, (&d, &s)) in shape.iter().zip(strides).enumerate() {
+            content.set_shape(prefix + i, d);
+            content.set_stride(prefix + i, s);
+        }
         ans
     }
+
+    /// 按照 NumPy 广播规则将布局广播到目标形状：右对齐后在前方补充新的阶，并扩增长度为 1 的阶。
+    ///
+    /// ```rust
+    /// # use ndarray_layout::ArrayLayout;
+    /// let layout = ArrayLayout::<3>::new(&[3, 1], &[1, 3], 0).broadcast_to(&[2, 3, 4]).unwrap();
+    /// assert_eq!(layout.shape(), &[2, 3, 4]);
+    /// assert_eq!(layout.strides(), &[0, 1, 0]);
+    /// assert_eq!(layout.offset(), 0);
+    /// ```
+    pub fn broadcast_to(&self, shape: &[usize]) -> Result<Self, BroadcastError> {
+        let ndim = shape.len();
+        let Some(prefix) = ndim.checked_sub(self.ndim) else {
+            return Err(BroadcastError::RankMismatch {
+                ndim: self.ndim,
+                target: ndim,
+            });
+        };
+
+        let content = self.content();
+        let src_shape = content.shape();
+        let src_strides = content.strides();
+
+        let mut ans = Self::with_ndim(ndim);
+        let mut content = ans.content_mut();
+        content.set_offset(self.offset());
+        content.copy_shape(shape);
+        for (axis, &target) in shape.iter().enumerate() {
+            let stride = match axis.checked_sub(prefix) {
+                None => 0,
+                Some(i) => match src_shape[i] {
+                    d if d == target => src_strides[i],
+                    1 => 0,
+                    dim => {
+                        return Err(BroadcastError::ShapeMismatch { axis, dim, target });
+                    }
+                },
+            };
+            content.set_stride(axis, stride);
+        }
+        Ok(ans)
+    }
+
+    /// 将布局广播到另一个布局的形状，尾部的阶自动对齐。
+    ///
+    /// ```rust
+    /// # use ndarray_layout::ArrayLayout;
+    /// let bias = ArrayLayout::<2>::new(&[4], &[1], 0);
+    /// let x = ArrayLayout::<3>::new(&[2, 3, 4], &[12, 4, 1], 0);
+    /// let layout = bias.broadcast_like(&x).unwrap();
+    /// assert_eq!(layout.shape(), &[2, 3, 4]);
+    /// assert_eq!(layout.strides(), &[0, 0, 1]);
+    /// assert_eq!(layout.offset(), 0);
+    /// ```
+    #[inline]
+    pub fn broadcast_like<const M: usize>(
+        &self,
+        other: &ArrayLayout<M>,
+    ) -> Result<Self, BroadcastError> {
+        self.broadcast_to(other.shape())
+    }
+
+    /// 按照 NumPy 广播规则计算两个布局的公共形状，并将两个布局都广播到公共形状。
+    ///
+    /// ```rust
+    /// # use ndarray_layout::ArrayLayout;
+    /// let a = ArrayLayout::<3>::new(&[2, 1, 4], &[4, 4, 1], 0);
+    /// let b = ArrayLayout::<3>::new(&[3, 1], &[1, 1], 0);
+    /// let (a, b) = a.broadcast_with(&b).unwrap();
+    /// assert_eq!(a.shape(), &[2, 3, 4]);
+    /// assert_eq!(a.strides(), &[4, 0, 1]);
+    /// assert_eq!(b.shape(), &[2, 3, 4]);
+    /// assert_eq!(b.strides(), &[0, 1, 0]);
+    /// ```
+    pub fn broadcast_with(&self, other: &Self) -> Result<(Self, Self), BroadcastError> {
+        let a = self.shape();
+        let b = other.shape();
+        let ndim = a.len().max(b.len());
+        let mut shape = vec![0; ndim];
+        for (axis, d) in shape.iter_mut().enumerate() {
+            let get = |s: &[usize]| (axis + s.len()).checked_sub(ndim).map_or(1, |i| s[i]);
+            *d = match (get(a), get(b)) {
+                (a, b) if a == b => a,
+                (1, b) => b,
+                (a, 1) => a,
+                (dim, target) => return Err(BroadcastError::ShapeMismatch { axis, dim, target }),
+            };
+        }
+        Ok((self.broadcast_to(&shape)?, other.broadcast_to(&shape)?))
+    }
 }
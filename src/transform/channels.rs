@@ -0,0 +1,61 @@
+﻿use crate::ArrayLayout;
+
+impl<const N: usize> ArrayLayout<N> {
+    /// 将通道在前的 4 阶或 5 阶张量（`NCHW`/`NCDHW`）转置为通道在后（`NHWC`/`NDHWC`），
+    /// 同时返回转置后的布局是否以 `element_size` 为元素大小按行优先紧密排布，
+    /// 判断方式同 [`is_c_contiguous`](Self::is_c_contiguous)。
+    ///
+    /// ```rust
+    /// # use ndarray_layout::{ArrayLayout, Endian};
+    /// let nhwc = ArrayLayout::<4>::new_contiguous(&[2, 3, 4, 5], Endian::BigEndian, 1);
+    /// let (nchw, packed) = nhwc.to_channels_first(1);
+    /// assert_eq!(nchw.shape(), &[2, 5, 3, 4]);
+    /// assert_eq!(nchw.strides(), &[60, 1, 20, 5]);
+    /// assert!(!packed);
+    ///
+    /// let (nhwc, packed) = nchw.to_channels_last(1);
+    /// assert_eq!(nhwc.shape(), &[2, 3, 4, 5]);
+    /// assert_eq!(nhwc.strides(), &[60, 20, 5, 1]);
+    /// assert!(packed);
+    /// ```
+    pub fn to_channels_last(&self, element_size: usize) -> (Self, bool) {
+        let ans = match self.ndim {
+            4 => self.transpose(&[0, 2, 3, 1]),
+            5 => self.transpose(&[0, 2, 3, 4, 1]),
+            n => panic!("channels last requires a 4-D or 5-D layout, got {n}-D"),
+        };
+        let packed = ans.is_c_contiguous(element_size);
+        (ans, packed)
+    }
+
+    /// 将通道在后的 4 阶或 5 阶张量（`NHWC`/`NDHWC`）转置为通道在前（`NCHW`/`NCDHW`），
+    /// 同时返回转置后的布局是否按行优先紧密排布，判断方式同 [`to_channels_last`](Self::to_channels_last)。
+    pub fn to_channels_first(&self, element_size: usize) -> (Self, bool) {
+        let ans = match self.ndim {
+            4 => self.transpose(&[0, 3, 1, 2]),
+            5 => self.transpose(&[0, 4, 1, 2, 3]),
+            n => panic!("channels first requires a 4-D or 5-D layout, got {n}-D"),
+        };
+        let packed = ans.is_c_contiguous(element_size);
+        (ans, packed)
+    }
+}
+
+#[test]
+fn test() {
+    // 广播的布局不是紧密排布的
+    let layout = ArrayLayout::<4>::new(&[2, 3, 4, 5], &[0; 4], 0);
+    assert!(!layout.to_channels_last(1).1);
+    assert!(!layout.to_channels_first(1).1);
+
+    // 步长为负的布局不是紧密排布的
+    let layout = ArrayLayout::<4>::new(&[2, 5, 3, 4], &[-60, -1, -20, -5], 119);
+    let (nhwc, packed) = layout.to_channels_last(1);
+    assert_eq!(nhwc.strides(), &[-60, -20, -5, -1]);
+    assert!(!packed);
+
+    // 元素大小参与判断
+    let layout = ArrayLayout::<4>::new(&[2, 5, 3, 4], &[240, 4, 80, 20], 0);
+    assert!(layout.to_channels_last(4).1);
+    assert!(!layout.to_channels_last(2).1);
+}
@@ -0,0 +1,39 @@
+﻿use crate::ArrayLayout;
+
+impl<const N: usize> ArrayLayout<N> {
+    /// 翻转变换将张量指定阶上元素的顺序倒置。
+    ///
+    /// ```rust
+    /// # use ndarray_layout::ArrayLayout;
+    /// let layout = ArrayLayout::<3>::new(&[2, 3, 4], &[12, 4, 1], 0).flip(1);
+    /// assert_eq!(layout.shape(), &[2, 3, 4]);
+    /// assert_eq!(layout.strides(), &[12, -4, 1]);
+    /// assert_eq!(layout.offset(), 8);
+    /// ```
+    #[inline]
+    pub fn flip(&self, axis: usize) -> Self {
+        self.flip_many(&[axis])
+    }
+
+    /// 一次对多个阶进行翻转变换。
+    pub fn flip_many(&self, axes: &[usize]) -> Self {
+        let mut ans = self.clone();
+        let mut content = ans.content_mut();
+        let mut offset = content.offset();
+        let mut last = None;
+        for &axis in axes {
+            assert!(
+                last.is_none_or(|last| last < axis),
+                "Flip axes must be in ascending order"
+            );
+            last = Some(axis);
+
+            let d = content.shape()[axis];
+            let s = content.strides()[axis];
+            offset += d.saturating_sub(1) as isize * s;
+            content.set_stride(axis, -s);
+        }
+        content.set_offset(offset);
+        ans
+    }
+}
@@ -0,0 +1,106 @@
+﻿use crate::ArrayLayout;
+
+/// 收集变换的计划。
+///
+/// 任意索引的选择无法表示为单个带步长的布局，因此收集变换产生一个由公共子布局和一组偏移组成的计划，
+/// 输出中沿收集阶的第 `i` 项等价于以 `offsets()[i]` 为偏移的子布局。
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct GatherPlan<const N: usize> {
+    axis: usize,
+    shape: Vec<usize>,
+    strides: Vec<isize>,
+    offsets: Vec<isize>,
+}
+
+impl<const N: usize> ArrayLayout<N> {
+    /// 收集变换沿指定阶按任意顺序选择若干项，索引可以重复。
+    ///
+    /// ```rust
+    /// # use ndarray_layout::ArrayLayout;
+    /// let plan = ArrayLayout::<3>::new(&[4, 3], &[3, 1], 0).gather(0, &[2, 0, 2]);
+    /// assert_eq!(plan.axis(), 0);
+    /// assert_eq!(plan.offsets(), &[6, 0, 6]);
+    ///
+    /// let layout = plan.get(0);
+    /// assert_eq!(layout.shape(), &[3]);
+    /// assert_eq!(layout.strides(), &[1]);
+    /// assert_eq!(layout.offset(), 6);
+    /// ```
+    pub fn gather(&self, axis: usize, indices: &[usize]) -> GatherPlan<N> {
+        let content = self.content();
+        let shape = content.shape();
+        let strides = content.strides();
+        let d = shape[axis];
+        let s = strides[axis];
+
+        let offset = content.offset();
+        let offsets = indices
+            .iter()
+            .map(|&i| {
+                assert!(i < d, "index {i} out of range {d}");
+                offset + i as isize * s
+            })
+            .collect();
+
+        let mut shape = shape.to_vec();
+        let mut strides = strides.to_vec();
+        shape.remove(axis);
+        strides.remove(axis);
+        GatherPlan {
+            axis,
+            shape,
+            strides,
+            offsets,
+        }
+    }
+}
+
+impl<const N: usize> GatherPlan<N> {
+    /// 收集的阶。
+    #[inline]
+    pub fn axis(&self) -> usize {
+        self.axis
+    }
+
+    /// 收集结果沿收集阶的长度。
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.offsets.len()
+    }
+
+    /// 收集结果是否为空。
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.offsets.is_empty()
+    }
+
+    /// 每一项子布局的形状。
+    #[inline]
+    pub fn shape(&self) -> &[usize] {
+        &self.shape
+    }
+
+    /// 每一项子布局的步长。
+    #[inline]
+    pub fn strides(&self) -> &[isize] {
+        &self.strides
+    }
+
+    /// 每一项子布局的偏移。
+    #[inline]
+    pub fn offsets(&self) -> &[isize] {
+        &self.offsets
+    }
+
+    /// 获取第 `i` 项的子布局。
+    #[inline]
+    pub fn get(&self, i: usize) -> ArrayLayout<N> {
+        ArrayLayout::new(&self.shape, &self.strides, self.offsets[i])
+    }
+
+    /// 依次产生每一项的子布局。
+    #[inline]
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = ArrayLayout<N>> + '_ {
+        (0..self.len()).map(|i| self.get(i))
+    }
+}
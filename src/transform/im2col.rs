@@ -0,0 +1,72 @@
+﻿use crate::ArrayLayout;
+
+/// im2col 变换参数，两个元素分别对应高和宽两个阶。
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Im2colArg {
+    /// 卷积核的大小。
+    pub kernel: [usize; 2],
+    /// 卷积的步长。
+    pub stride: [usize; 2],
+    /// 卷积的膨胀。
+    pub dilation: [usize; 2],
+}
+
+impl<const N: usize> ArrayLayout<N> {
+    /// im2col 变换将 `[n, c, h, w]` 形状的图像布局展开为 `[n, oh, ow, c, kh, kw]` 形状的卷积窗口布局。
+    /// 将前 3 阶视作行、后 3 阶视作列，即得到 im2col 矩阵。
+    ///
+    /// ```rust
+    /// # use ndarray_layout::{ArrayLayout, Im2colArg};
+    /// let layout = ArrayLayout::<6>::new(&[1, 2, 5, 5], &[50, 25, 5, 1], 0).im2col(&Im2colArg {
+    ///     kernel: [3, 3],
+    ///     stride: [2, 2],
+    ///     dilation: [1, 1],
+    /// });
+    /// assert_eq!(layout.shape(), &[1, 2, 2, 2, 3, 3]);
+    /// assert_eq!(layout.strides(), &[50, 10, 2, 25, 5, 1]);
+    /// assert_eq!(layout.offset(), 0);
+    /// ```
+    pub fn im2col(&self, arg: &Im2colArg) -> Self {
+        let &Im2colArg {
+            kernel,
+            stride,
+            dilation,
+        } = arg;
+        let &[n, c, h, w] = self.shape() else {
+            panic!("im2col requires a 4-D layout, got {}-D", self.ndim)
+        };
+        let &[sn, sc, sh, sw] = self.strides() else {
+            unreachable!()
+        };
+
+        let mut out = [0; 2];
+        for (i, d) in [h, w].into_iter().enumerate() {
+            assert!(kernel[i] > 0 && stride[i] > 0 && dilation[i] > 0);
+            let receptive = dilation[i] * (kernel[i] - 1) + 1;
+            assert!(receptive <= d, "receptive field {receptive} exceeds {d}");
+            out[i] = (d - receptive) / stride[i] + 1;
+        }
+
+        let [kh, kw] = kernel;
+        let [oh, ow] = out;
+        let [ph, pw] = stride.map(|s| s as isize);
+        let [dh, dw] = dilation.map(|d| d as isize);
+        Self::new(
+            &[n, oh, ow, c, kh, kw],
+            &[sn, sh * ph, sw * pw, sc, sh * dh, sw * dw],
+            self.offset(),
+        )
+    }
+}
+
+#[test]
+fn test() {
+    let layout = ArrayLayout::<4>::new(&[2, 3, 7, 6], &[126, 42, 6, 1], 0).im2col(&Im2colArg {
+        kernel: [2, 3],
+        stride: [1, 3],
+        dilation: [3, 1],
+    });
+    assert_eq!(layout.shape(), &[2, 4, 2, 3, 2, 3]);
+    assert_eq!(layout.strides(), &[126, 6, 3, 42, 18, 1]);
+    assert_eq!(layout.offset(), 0);
+}
@@ -2,7 +2,7 @@
 use std::iter::zip;
 
 /// 索引变换参数。
-#[derive(Clone, PartialEq, Eq, Debug)]
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
 pub struct IndexArg {
     /// 索引的轴。
     pub axis: usize,
@@ -10,6 +10,15 @@ pub struct IndexArg {
     pub index: usize,
 }
 
+/// 沿某个阶逐个索引的迭代器。
+#[derive(Clone)]
+pub struct AxisIter<'a, const N: usize> {
+    src: &'a ArrayLayout<N>,
+    axis: usize,
+    start: usize,
+    end: usize,
+}
+
 impl<const N: usize> ArrayLayout<N> {
     /// 索引变换是选择张量指定阶上一项数据的变换，例如指定向量中的一个数、指定矩阵的一行或一列。
     /// 索引变换导致张量降阶，确定索引的阶从张量表示移除。
@@ -25,6 +34,50 @@ impl<const N: usize> ArrayLayout<N> {
         self.index_many(&[IndexArg { axis, index }])
     }
 
+    /// 沿指定阶逐个进行索引变换，依次产生降低一阶的子布局。
+    ///
+    /// ```rust
+    /// # use ndarray_layout::ArrayLayout;
+    /// let layout = ArrayLayout::<3>::new(&[2, 3, 4], &[12, 4, 1], 0);
+    /// let rows = layout.iter_axis(1).collect::<Vec<_>>();
+    /// assert_eq!(rows.len(), 3);
+    /// assert_eq!(rows[2].shape(), &[2, 4]);
+    /// assert_eq!(rows[2].strides(), &[12, 1]);
+    /// assert_eq!(rows[2].offset(), 8);
+    /// ```
+    #[inline]
+    pub fn iter_axis(&self, axis: usize) -> AxisIter<'_, N> {
+        AxisIter::new(self, axis)
+    }
+
+    /// 保留维度的索引变换，选择指定阶上的一项数据，但保留该阶并使其长度为 1。
+    ///
+    /// ```rust
+    /// # use ndarray_layout::ArrayLayout;
+    /// let layout = ArrayLayout::<3>::new(&[2, 3, 4], &[12, 4, 1], 0).index_keepdim(1, 2);
+    /// assert_eq!(layout.shape(), &[2, 1, 4]);
+    /// assert_eq!(layout.strides(), &[12, 4, 1]);
+    /// assert_eq!(layout.offset(), 8);
+    /// ```
+    pub fn index_keepdim(&self, axis: usize, index: usize) -> Self {
+        let d = self.shape()[axis];
+        assert!(index < d, "index {index} out of range for dim {d}");
+        self.slice(axis, index, 1, 1)
+    }
+
+    /// 允许负数索引的索引变换，负数索引从该阶末尾倒数。
+    ///
+    /// ```rust
+    /// # use ndarray_layout::ArrayLayout;
+    /// let layout = ArrayLayout::<3>::new(&[2, 3, 4], &[12, 4, 1], 0).index_signed(1, -1);
+    /// assert_eq!(layout.shape(), &[2, 4]);
+    /// assert_eq!(layout.strides(), &[12, 1]);
+    /// assert_eq!(layout.offset(), 8);
+    /// ```
+    pub fn index_signed(&self, axis: usize, index: isize) -> Self {
+        self.index(axis, resolve_signed(self.shape()[axis], index))
+    }
+
     /// 一次对多个阶进行索引变换。
     pub fn index_many(&self, mut args: &[IndexArg]) -> Self {
         let content = self.content();
@@ -65,6 +118,58 @@ impl<const N: usize> ArrayLayout<N> {
     }
 }
 
+/// 将可能为负数的索引转换为 `[0, d)` 范围内的索引。
+pub(crate) fn resolve_signed(d: usize, index: isize) -> usize {
+    let ans = if index < 0 {
+        d.checked_sub(index.unsigned_abs())
+    } else {
+        Some(index as usize).filter(|&i| i < d)
+    };
+    ans.unwrap_or_else(|| panic!("index {index} out of range for dim {d}"))
+}
+
+impl<'a, const N: usize> AxisIter<'a, N> {
+    #[inline]
+    pub(crate) fn new(src: &'a ArrayLayout<N>, axis: usize) -> Self {
+        Self {
+            src,
+            axis,
+            start: 0,
+            end: src.shape()[axis],
+        }
+    }
+}
+
+impl<const N: usize> Iterator for AxisIter<'_, N> {
+    type Item = ArrayLayout<N>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        (self.start < self.end).then(|| {
+            self.start += 1;
+            self.src.index(self.axis, self.start - 1)
+        })
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.end - self.start;
+        (len, Some(len))
+    }
+}
+
+impl<const N: usize> DoubleEndedIterator for AxisIter<'_, N> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        (self.start < self.end).then(|| {
+            self.end -= 1;
+            self.src.index(self.axis, self.end)
+        })
+    }
+}
+
+impl<const N: usize> ExactSizeIterator for AxisIter<'_, N> {}
+
 #[test]
 fn test() {
     let layout = ArrayLayout::<1>::new(&[2, 3, 4], &[12, 4, 1], 0);
@@ -0,0 +1,57 @@
+﻿use crate::ArrayLayout;
+
+impl<const N: usize> ArrayLayout<N> {
+    /// 插入变换在指定位置插入一个长度为 1、步长为 0 的阶。
+    ///
+    /// ```rust
+    /// # use ndarray_layout::ArrayLayout;
+    /// let layout = ArrayLayout::<3>::new(&[3, 4], &[4, 1], 0).insert_axis(1);
+    /// assert_eq!(layout.shape(), &[3, 1, 4]);
+    /// assert_eq!(layout.strides(), &[4, 0, 1]);
+    /// assert_eq!(layout.offset(), 0);
+    /// ```
+    #[inline]
+    pub fn insert_axis(&self, axis: usize) -> Self {
+        self.insert_axis_many(&[axis])
+    }
+
+    /// 一次插入多个阶，阶号是插入后张量中的位置，必须升序排列。
+    ///
+    /// ```rust
+    /// # use ndarray_layout::ArrayLayout;
+    /// let layout = ArrayLayout::<3>::new(&[3, 4], &[4, 1], 0).insert_axis_many(&[0, 3]);
+    /// assert_eq!(layout.shape(), &[1, 3, 4, 1]);
+    /// assert_eq!(layout.strides(), &[0, 4, 1, 0]);
+    /// assert_eq!(layout.offset(), 0);
+    /// ```
+    pub fn insert_axis_many(&self, mut axes: &[usize]) -> Self {
+        let content = self.content();
+        let shape = content.shape();
+        let strides = content.strides();
+        let ndim = self.ndim + axes.len();
+
+        let mut ans = Self::with_ndim(ndim);
+        let mut content = ans.content_mut();
+        content.set_offset(self.offset());
+        let mut i = 0;
+        for j in 0..ndim {
+            match *axes {
+                [axis, ref tail @ ..] if axis == j => {
+                    if let [next, ..] = tail {
+                        assert!(next > &axis, "Insert axes must be in ascending order");
+                    }
+                    content.set_shape(j, 1);
+                    content.set_stride(j, 0);
+                    axes = tail;
+                }
+                [..] => {
+                    content.set_shape(j, shape[i]);
+                    content.set_stride(j, strides[i]);
+                    i += 1;
+                }
+            }
+        }
+        assert!(axes.is_empty(), "Invalid insert axes: {axes:?}");
+        ans
+    }
+}
@@ -1,5 +1,42 @@
 ﻿use crate::ArrayLayout;
-use std::{iter::zip, ops::Range};
+use std::{
+    error::Error,
+    fmt,
+    iter::zip,
+    ops::{Bound, Range, RangeBounds},
+};
+
+/// 合并错误。
+///
+/// 按步长绝对值排序后相邻的两个阶之间不满足 `strides[1] == strides[0] * shape[0]`，合并需要拷贝数据。
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct MergeError {
+    /// 无法合并的两个阶，步长绝对值较小的在前。
+    pub axes: [usize; 2],
+    /// 两个阶的长度。
+    pub shape: [usize; 2],
+    /// 两个阶的步长。
+    pub strides: [isize; 2],
+}
+
+impl fmt::Display for MergeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Self {
+            axes: [a, b],
+            shape: [da, db],
+            strides: [sa, sb],
+        } = *self;
+        write!(
+            f,
+            "cannot merge axis {a} (dim {da}, stride {sa}) with axis {b} (dim {db}, stride {sb}) without copying"
+        )
+    }
+}
+
+impl Error for MergeError {}
+
+/// 合并时在栈上排序的最大阶数。
+const INLINE_AXES: usize = 8;
 
 impl<const N: usize> ArrayLayout<N> {
     /// 合并变换是将多个连续维度划分合并的变换。
@@ -16,14 +53,158 @@ impl<const N: usize> ArrayLayout<N> {
         self.merge_many(&[range])
     }
 
+    /// 展平变换合并 `range` 范围内的所有阶，`..` 表示展平为 1 阶。
+    ///
+    /// ```rust
+    /// # use ndarray_layout::ArrayLayout;
+    /// let layout = ArrayLayout::<3>::new(&[2, 3, 4], &[12, 4, 1], 0).flatten(..).unwrap();
+    /// assert_eq!(layout.shape(), &[24]);
+    /// assert_eq!(layout.strides(), &[1]);
+    /// assert_eq!(layout.offset(), 0);
+    /// ```
+    pub fn flatten(&self, range: impl RangeBounds<usize>) -> Option<Self> {
+        let start = match range.start_bound() {
+            Bound::Included(&i) => i,
+            Bound::Excluded(&i) => i + 1,
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(&i) => i + 1,
+            Bound::Excluded(&i) => i,
+            Bound::Unbounded => self.ndim,
+        };
+        assert!(start <= end && end <= self.ndim, "invalid flatten range");
+        if end - start < 2 {
+            Some(self.clone())
+        } else {
+            self.merge(start..end)
+        }
+    }
+
+    /// 保留前 `ndim - 1` 阶，将其余的阶合并为最后一阶，使张量的阶数降为 `ndim`。
+    ///
+    /// ```rust
+    /// # use ndarray_layout::ArrayLayout;
+    /// let layout = ArrayLayout::<4>::new(&[2, 3, 4, 5], &[60, 20, 5, 1], 0).flatten_to(2).unwrap();
+    /// assert_eq!(layout.shape(), &[2, 60]);
+    /// assert_eq!(layout.strides(), &[60, 1]);
+    /// assert_eq!(layout.offset(), 0);
+    /// ```
+    pub fn flatten_to(&self, ndim: usize) -> Option<Self> {
+        assert!(ndim > 0 || self.ndim == 0, "cannot flatten to 0-D");
+        if ndim >= self.ndim {
+            Some(self.clone())
+        } else {
+            self.flatten(ndim - 1..)
+        }
+    }
+
+    /// 合并若干不一定相邻的阶。先将 `axes` 按给定顺序移动到其中最小阶的位置使其相邻，再进行合并。
+    /// 同时返回所用的置换，置换的含义同 [`permute`](Self::permute)。
+    ///
+    /// ```rust
+    /// # use ndarray_layout::ArrayLayout;
+    /// // [batch, seq, head, dim] 融合 batch 和 head
+    /// let layout = ArrayLayout::<4>::new(&[2, 5, 3, 4], &[12, 24, 4, 1], 0);
+    /// let (merged, perm) = layout.merge_permuted(&[0, 2]).unwrap();
+    /// assert_eq!(perm, [0, 2, 1, 3]);
+    /// assert_eq!(merged.shape(), &[6, 5, 4]);
+    /// assert_eq!(merged.strides(), &[4, 24, 1]);
+    /// assert_eq!(merged.offset(), 0);
+    /// ```
+    ///
+    /// 无法合并时返回 `None`。
+    ///
+    /// # Panics
+    ///
+    /// `axes` 中的阶超出范围或重复出现时 panic。
+    pub fn merge_permuted(&self, axes: &[usize]) -> Option<(Self, Vec<usize>)> {
+        for (i, &axis) in axes.iter().enumerate() {
+            assert!(axis < self.ndim, "merge axis {axis} out of range");
+            assert!(
+                !axes[..i].contains(&axis),
+                "merge axis {axis} appears more than once"
+            );
+        }
+        let Some(&pos) = axes.iter().min() else {
+            return Some((self.clone(), (0..self.ndim).collect()));
+        };
+        let mut perm = (0..pos).filter(|i| !axes.contains(i)).collect::<Vec<_>>();
+        let start = perm.len();
+        perm.extend_from_slice(axes);
+        perm.extend((pos..self.ndim).filter(|i| !axes.contains(i)));
+
+        let merged = self
+            .permute(&perm)
+            .unwrap()
+            .merge(start..start + axes.len())?;
+        Some((merged, perm))
+    }
+
+    /// 合并所有可以合并的相邻阶，并移除长度为 1 的阶，使张量的阶数尽可能少。
+    ///
+    /// ```rust
+    /// # use ndarray_layout::ArrayLayout;
+    /// let layout = ArrayLayout::<6>::new(&[2, 3, 1, 4, 5, 6], &[0, 0, 7, 30, 6, 1], 0).coalesce();
+    /// assert_eq!(layout.shape(), &[6, 120]);
+    /// assert_eq!(layout.strides(), &[0, 1]);
+    /// assert_eq!(layout.offset(), 0);
+    /// ```
+    pub fn coalesce(&self) -> Self {
+        let mut dims = Vec::<(usize, isize)>::with_capacity(self.ndim);
+        for (&d, &s) in zip(self.shape(), self.strides()) {
+            match dims.last_mut() {
+                _ if d == 1 => {}
+                Some((d_, s_)) if *s_ == s * d as isize => {
+                    *d_ *= d;
+                    *s_ = s;
+                }
+                _ => dims.push((d, s)),
+            }
+        }
+
+        let mut ans = Self::with_ndim(dims.len());
+        let mut content = ans.content_mut();
+        content.set_offset(self.offset());
+        for (i, (d, s)) in dims.into_iter().enumerate() {
+            content.set_shape(i, d);
+            content.set_stride(i, s);
+        }
+        ans
+    }
+
     /// 一次对多个阶进行合并变换。
+    #[inline]
     pub fn merge_many(&self, args: &[Range<usize>]) -> Option<Self> {
+        self.try_merge_many(args).ok()
+    }
+
+    /// 一次对多个阶进行合并变换，无法合并时返回导致失败的两个阶。
+    ///
+    /// 结果的阶数为 `ndim - Σ(len - 1)`，空的范围被忽略，不会增加或减少阶。
+    ///
+    /// ```rust
+    /// # use ndarray_layout::{ArrayLayout, MergeError};
+    /// let layout = ArrayLayout::<3>::new(&[2, 3, 4], &[40, 8, 2], 0);
+    /// assert_eq!(
+    ///     layout.try_merge_many(&[0..3]).err(),
+    ///     Some(MergeError {
+    ///         axes: [1, 0],
+    ///         shape: [3, 2],
+    ///         strides: [8, 40],
+    ///     })
+    /// );
+    /// ```
+    pub fn try_merge_many(&self, args: &[Range<usize>]) -> Result<Self, MergeError> {
         let content = self.content();
         let shape = content.shape();
         let strides = content.strides();
 
-        let merged = args.iter().map(|range| range.len()).sum::<usize>();
-        let mut ans = Self::with_ndim(self.ndim + args.len() - merged);
+        let merged = args
+            .iter()
+            .map(|range| range.len().saturating_sub(1))
+            .sum::<usize>();
+        let mut ans = Self::with_ndim(self.ndim - merged);
 
         let mut content = ans.content_mut();
         content.set_offset(self.offset());
@@ -45,19 +226,36 @@ impl<const N: usize> ArrayLayout<N> {
                 push(shape[j], strides[j]);
             }
 
-            let mut pairs = zip(&shape[range.clone()], &strides[range.clone()]).collect::<Vec<_>>();
-            pairs.sort_unstable_by_key(|(_, &s)| s.unsigned_abs());
+            // 常见的合并范围不超过 INLINE_AXES 阶，在栈上排序以免分配
+            let mut buf = [0; INLINE_AXES];
+            let mut heap = Vec::new();
+            let key = |&i: &usize| strides[i].unsigned_abs();
+            let pairs = if range.len() <= INLINE_AXES {
+                let pairs = &mut buf[..range.len()];
+                zip(&mut *pairs, range.clone()).for_each(|(p, i)| *p = i);
+                insertion_sort_by_key(pairs, key);
+                pairs
+            } else {
+                heap.extend(range.clone());
+                heap.sort_by_key(key);
+                &mut heap[..]
+            };
 
-            let (&d, &s) = pairs[0];
-            let mut d = d;
+            let s = strides[pairs[0]];
+            let mut d = shape[pairs[0]];
 
             for i in 1..pairs.len() {
-                let (&l, &ls) = pairs[i - 1];
-                let (&r, &rs) = pairs[i];
+                let (la, ra) = (pairs[i - 1], pairs[i]);
+                let (l, ls) = (shape[la], strides[la]);
+                let (r, rs) = (shape[ra], strides[ra]);
                 if l == 1 || s == 1 || ls == rs * r as isize || rs == ls * l as isize {
                     d *= r;
                 } else {
-                    return None;
+                    return Err(MergeError {
+                        axes: [la, ra],
+                        shape: [l, r],
+                        strides: [ls, rs],
+                    });
                 }
             }
 
@@ -68,6 +266,70 @@ impl<const N: usize> ArrayLayout<N> {
             push(shape[j], strides[j]);
         }
 
-        Some(ans)
+        Ok(ans)
     }
 }
+
+/// 插入排序，用于少量元素的稳定排序。
+fn insertion_sort_by_key<T, K: Ord>(slice: &mut [T], key: impl Fn(&T) -> K) {
+    for i in 1..slice.len() {
+        let mut j = i;
+        while j > 0 && key(&slice[j - 1]) > key(&slice[j]) {
+            slice.swap(j - 1, j);
+            j -= 1;
+        }
+    }
+}
+
+#[test]
+fn test() {
+    use crate::Endian;
+    use std::slice::from_ref;
+
+    // 超出栈上缓冲的合并范围
+    let shape = [2; 10];
+    let layout = ArrayLayout::<4>::new_contiguous(&shape, Endian::BigEndian, 4);
+    let merged = layout.merge(0..10).unwrap();
+    assert_eq!(merged.shape(), &[1024]);
+    assert_eq!(merged.strides(), &[4]);
+
+    let layout = ArrayLayout::<4>::new_contiguous(&shape, Endian::LittleEndian, 4).flip(3);
+    assert!(layout.merge(0..10).is_none());
+    assert_eq!(layout.merge(4..10).unwrap().shape(), &[2, 2, 2, 2, 64]);
+
+    // 空的范围不改变阶数
+    let layout = ArrayLayout::<4>::new(&[2, 3, 4], &[12, 4, 1], 0);
+    assert_eq!(layout.merge(1..1).unwrap(), layout);
+    let merged = layout.merge_many(&[0..0, 1..3, 3..3]).unwrap();
+    assert_eq!(merged.shape(), &[2, 12]);
+    assert_eq!(merged.strides(), &[12, 1]);
+
+    // 步长相同的阶在栈上和堆上排序后顺序一致，报告的阶不随范围长度变化
+    let shape = [2; 10];
+    let strides = [4, 0, 8, 0, 16, 0, 32, 0, 64, 0];
+    let layout = ArrayLayout::<4>::new(&shape, &strides, 0);
+    let err = MergeError {
+        axes: [9, 0],
+        shape: [2, 2],
+        strides: [0, 4],
+    };
+    assert_eq!(layout.try_merge_many(from_ref(&(0..10))).err(), Some(err));
+    let err = MergeError {
+        axes: [5, 0],
+        shape: [2, 2],
+        strides: [0, 4],
+    };
+    assert_eq!(layout.try_merge_many(from_ref(&(0..6))).err(), Some(err));
+}
+
+#[test]
+#[should_panic(expected = "merge axis 5 out of range")]
+fn test_merge_permuted_out_of_range() {
+    let _ = ArrayLayout::<2>::new(&[2, 3], &[3, 1], 0).merge_permuted(&[0, 5]);
+}
+
+#[test]
+#[should_panic(expected = "merge axis 1 appears more than once")]
+fn test_merge_permuted_duplicate() {
+    let _ = ArrayLayout::<2>::new(&[2, 3], &[3, 1], 0).merge_permuted(&[1, 1]);
+}
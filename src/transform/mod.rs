@@ -1,13 +1,30 @@
-﻿mod broadcast;
+﻿mod block;
+mod broadcast;
+mod channels;
+mod flip;
+mod gather;
+mod im2col;
 mod index;
+mod insert;
 mod merge;
+mod reshape;
 mod slice;
+mod slice_expr;
 mod split;
+mod squeeze;
+mod stack;
 mod tile;
 mod transpose;
+mod unfold;
 
-pub use broadcast::BroadcastArg;
-pub use index::IndexArg;
+pub use broadcast::{BroadcastArg, BroadcastError};
+pub use gather::GatherPlan;
+pub use im2col::Im2colArg;
+pub use index::{AxisIter, IndexArg};
+pub use merge::MergeError;
+pub use reshape::ReshapeDim;
 pub use slice::SliceArg;
-pub use split::Split;
+pub use slice_expr::{SliceExpr, SliceExprError, SliceItem};
+pub use split::{Chunks, Split, SplitGrid};
 pub use tile::TileArg;
+pub use transpose::{invert_permutation, PermuteError};
@@ -0,0 +1,153 @@
+﻿use crate::ArrayLayout;
+
+/// 重塑变换的目标维度。
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum ReshapeDim {
+    /// 指定长度的维度。
+    Fixed(usize),
+    /// 由元素总数推断长度的维度，至多出现一次。
+    Infer,
+}
+
+impl<const N: usize> ArrayLayout<N> {
+    /// 重塑变换是在不移动数据的前提下改变张量形状的变换，相当于合并变换和分块变换的组合。
+    /// 如果当前布局的步长不支持无拷贝的重塑，返回 `None`。
+    ///
+    /// ```rust
+    /// # use ndarray_layout::ArrayLayout;
+    /// let layout = ArrayLayout::<3>::new(&[2, 3, 4], &[12, 4, 1], 0).reshape(&[6, 2, 2]).unwrap();
+    /// assert_eq!(layout.shape(), &[6, 2, 2]);
+    /// assert_eq!(layout.strides(), &[4, 2, 1]);
+    /// assert_eq!(layout.offset(), 0);
+    ///
+    /// let layout = ArrayLayout::<3>::new(&[2, 3, 4], &[4, 8, 1], 0);
+    /// assert!(layout.reshape(&[6, 4]).is_none());
+    /// ```
+    pub fn reshape(&self, new_shape: &[usize]) -> Option<Self> {
+        let content = self.content();
+        let shape = content.shape();
+        let strides = content.strides();
+        assert_eq!(
+            shape.iter().product::<usize>(),
+            new_shape.iter().product::<usize>(),
+            "reshape must not change the number of elements"
+        );
+
+        let mut ans = Self::with_ndim(new_shape.len());
+        let mut content = ans.content_mut();
+        content.set_offset(self.offset());
+        content.copy_shape(new_shape);
+
+        // 空张量不访问任何数据，步长无意义
+        if new_shape.contains(&0) {
+            (0..new_shape.len()).for_each(|i| content.set_stride(i, 0));
+            return Some(ans);
+        }
+
+        // 长度为 1 的阶不影响数据排布，直接忽略
+        let (old_d, old_s): (Vec<_>, Vec<_>) = shape
+            .iter()
+            .zip(strides)
+            .filter(|(&d, _)| d != 1)
+            .map(|(&d, &s)| (d, s))
+            .unzip();
+
+        // 将新旧形状划分为乘积相等的若干组，每组旧阶必须连续
+        let (mut oi, mut oj) = (0, 1);
+        let (mut ni, mut nj) = (0, 1);
+        while ni < new_shape.len() && oi < old_d.len() {
+            let mut np = new_shape[ni];
+            let mut op = old_d[oi];
+            while np != op {
+                if np < op {
+                    np *= new_shape[nj];
+                    nj += 1;
+                } else {
+                    op *= old_d[oj];
+                    oj += 1;
+                }
+            }
+
+            for k in oi..oj - 1 {
+                if old_s[k] != old_s[k + 1] * old_d[k + 1] as isize {
+                    return None;
+                }
+            }
+
+            let mut s = old_s[oj - 1];
+            for k in (ni..nj).rev() {
+                content.set_stride(k, s);
+                s *= new_shape[k] as isize;
+            }
+
+            ni = nj;
+            nj += 1;
+            oi = oj;
+            oj += 1;
+        }
+
+        // 剩余的新阶长度必然为 1
+        let last = if ni > 0 { content.strides()[ni - 1] } else { 0 };
+        (ni..new_shape.len()).for_each(|i| content.set_stride(i, last));
+        Some(ans)
+    }
+
+    /// 允许一个维度长度由元素总数推断的重塑变换。
+    ///
+    /// ```rust
+    /// # use ndarray_layout::{ArrayLayout, ReshapeDim::*};
+    /// let layout = ArrayLayout::<3>::new(&[2, 3, 4], &[12, 4, 1], 0);
+    /// let layout = layout.reshape_infer(&[Fixed(2), Infer, Fixed(3)]).unwrap();
+    /// assert_eq!(layout.shape(), &[2, 4, 3]);
+    /// assert_eq!(layout.strides(), &[12, 3, 1]);
+    /// assert_eq!(layout.offset(), 0);
+    /// ```
+    pub fn reshape_infer(&self, new_shape: &[ReshapeDim]) -> Option<Self> {
+        let mut infer = None;
+        let mut fixed = 1;
+        let mut shape = Vec::with_capacity(new_shape.len());
+        for (i, dim) in new_shape.iter().enumerate() {
+            match *dim {
+                ReshapeDim::Fixed(d) => {
+                    fixed *= d;
+                    shape.push(d);
+                }
+                ReshapeDim::Infer => {
+                    assert!(infer.is_none(), "only one dimension can be inferred");
+                    infer = Some(i);
+                    shape.push(1);
+                }
+            }
+        }
+        if let Some(i) = infer {
+            let total = self.shape().iter().product::<usize>();
+            assert!(
+                fixed != 0 && total % fixed == 0,
+                "cannot infer dimension: {total} elements into fixed product {fixed}"
+            );
+            shape[i] = total / fixed;
+        }
+        self.reshape(&shape)
+    }
+}
+
+#[test]
+fn test() {
+    let layout = ArrayLayout::<3>::new(&[2, 3, 4], &[12, 4, 1], 0);
+    let layout = layout.reshape(&[1, 24, 1]).unwrap();
+    assert_eq!(layout.shape(), &[1, 24, 1]);
+    assert_eq!(layout.strides(), &[24, 1, 1]);
+
+    let layout = ArrayLayout::<3>::new(&[2, 1, 3, 4], &[-12, 7, 4, 1], 12);
+    let layout = layout.reshape(&[2, 12]).unwrap();
+    assert_eq!(layout.shape(), &[2, 12]);
+    assert_eq!(layout.strides(), &[-12, 1]);
+    assert_eq!(layout.offset(), 12);
+
+    let layout = ArrayLayout::<3>::new(&[4, 6], &[1, 4], 0);
+    let layout = layout.reshape(&[2, 2, 3, 2]).unwrap();
+    assert_eq!(layout.strides(), &[2, 1, 8, 4]);
+    assert!(ArrayLayout::<3>::new(&[4, 6], &[1, 4], 0)
+        .reshape(&[24])
+        .is_none());
+}
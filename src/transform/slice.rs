@@ -1,8 +1,9 @@
-﻿use crate::ArrayLayout;
-use std::iter::zip;
+﻿use super::index::resolve_signed;
+use crate::ArrayLayout;
+use std::ops::{Bound, RangeBounds};
 
 /// 切片变换参数。
-#[derive(Clone, PartialEq, Eq, Debug)]
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
 pub struct SliceArg {
     /// 切片的轴。
     pub axis: usize,
@@ -34,17 +35,176 @@ impl<const N: usize> ArrayLayout<N> {
         }])
     }
 
+    /// 收窄变换选择指定阶上从 `start` 开始的连续 `len` 个元素。
+    ///
+    /// ```rust
+    /// # use ndarray_layout::ArrayLayout;
+    /// let layout = ArrayLayout::<3>::new(&[2, 3, 4], &[12, 4, 1], 0).narrow(2, 1, 3);
+    /// assert_eq!(layout.shape(), &[2, 3, 3]);
+    /// assert_eq!(layout.strides(), &[12, 4, 1]);
+    /// assert_eq!(layout.offset(), 1);
+    /// ```
+    pub fn narrow(&self, axis: usize, start: usize, len: usize) -> Self {
+        let d = self.shape()[axis];
+        assert!(
+            start.checked_add(len).is_some_and(|end| end <= d),
+            "narrow [{start}, {start} + {len}) out of range for dim {d}"
+        );
+        self.slice_clamped(axis, start, 1, len)
+    }
+
+    /// 允许负数起始位置的切片变换，负数起始位置从该阶末尾倒数。
+    ///
+    /// ```rust
+    /// # use ndarray_layout::ArrayLayout;
+    /// // axis = 2, start = -3, step = 1, len = 2
+    /// let layout = ArrayLayout::<3>::new(&[2, 3, 4], &[12, 4, 1], 0).slice_signed(2, -3, 1, 2);
+    /// assert_eq!(layout.shape(), &[2, 3, 2]);
+    /// assert_eq!(layout.strides(), &[12, 4, 1]);
+    /// assert_eq!(layout.offset(), 1);
+    /// ```
+    pub fn slice_signed(&self, axis: usize, start: isize, step: isize, len: usize) -> Self {
+        let start = resolve_signed(self.shape()[axis], start);
+        self.slice(axis, start, step, len)
+    }
+
+    /// 以范围表示的切片变换，选择 `range` 中步长为 `step` 的元素，超出该阶的范围被截断。
+    /// 负数步长从范围末尾开始倒序选择。
+    ///
+    /// ```rust
+    /// # use ndarray_layout::ArrayLayout;
+    /// let layout = ArrayLayout::<3>::new(&[2, 3, 6], &[18, 6, 1], 0).slice_range(2, 1..5, 2);
+    /// assert_eq!(layout.shape(), &[2, 3, 2]);
+    /// assert_eq!(layout.strides(), &[18, 6, 2]);
+    /// assert_eq!(layout.offset(), 1);
+    ///
+    /// let layout = ArrayLayout::<3>::new(&[2, 3, 6], &[18, 6, 1], 0).slice_range(2, ..=3, -1);
+    /// assert_eq!(layout.shape(), &[2, 3, 4]);
+    /// assert_eq!(layout.strides(), &[18, 6, -1]);
+    /// assert_eq!(layout.offset(), 3);
+    /// ```
+    pub fn slice_range(&self, axis: usize, range: impl RangeBounds<usize>, step: isize) -> Self {
+        assert_ne!(step, 0, "step must not be zero");
+        let d = self.shape()[axis];
+        let s = self.strides()[axis];
+
+        let end = match range.end_bound() {
+            Bound::Included(&e) => e.saturating_add(1),
+            Bound::Excluded(&e) => e,
+            Bound::Unbounded => d,
+        }
+        .min(d);
+        let start = match range.start_bound() {
+            Bound::Included(&s) => s,
+            Bound::Excluded(&s) => s.saturating_add(1),
+            Bound::Unbounded => 0,
+        }
+        .min(end);
+
+        let n = end - start;
+        let (first, len) = if step > 0 {
+            (start, n.div_ceil(step as _))
+        } else {
+            (end.saturating_sub(1), n.div_ceil((-step) as _))
+        };
+
+        let mut ans = self.clone();
+        let mut content = ans.content_mut();
+        if len > 0 {
+            content.set_offset(self.offset() + first as isize * s);
+        }
+        content.set_shape(axis, len);
+        content.set_stride(axis, s * step);
+        ans
+    }
+
+    /// 饱和的切片变换，超出范围的起始位置和长度被截断，可能产生长度为 0 的阶。
+    ///
+    /// ```rust
+    /// # use ndarray_layout::ArrayLayout;
+    /// let layout = ArrayLayout::<3>::new(&[2, 3, 4], &[12, 4, 1], 0).slice_clamped(2, 2, 1, 5);
+    /// assert_eq!(layout.shape(), &[2, 3, 2]);
+    /// assert_eq!(layout.strides(), &[12, 4, 1]);
+    /// assert_eq!(layout.offset(), 2);
+    ///
+    /// let layout = ArrayLayout::<3>::new(&[2, 3, 4], &[12, 4, 1], 0).slice_clamped(2, 7, 1, 5);
+    /// assert_eq!(layout.shape(), &[2, 3, 0]);
+    /// ```
+    pub fn slice_clamped(&self, axis: usize, start: usize, step: isize, len: usize) -> Self {
+        let d = self.shape()[axis];
+        let s = self.strides()[axis];
+        use std::cmp::Ordering::*;
+        let (start, len) = match step.cmp(&0) {
+            _ if d == 0 => (0, 0),
+            Greater if start >= d => (0, 0),
+            Greater => (start, (d - start).div_ceil(step as _).min(len)),
+            Equal if start >= d => (0, 0),
+            Equal => (start, len),
+            Less => {
+                let start = start.min(d - 1);
+                (start, (start + 1).div_ceil((-step) as _).min(len))
+            }
+        };
+
+        let mut ans = self.clone();
+        let mut content = ans.content_mut();
+        content.set_offset(self.offset() + start as isize * s);
+        content.set_shape(axis, len);
+        content.set_stride(axis, s * step);
+        ans
+    }
+
+    /// 在指定阶上每隔 `n` 个元素选择一个元素，长度自动计算。
+    ///
+    /// ```rust
+    /// # use ndarray_layout::ArrayLayout;
+    /// let layout = ArrayLayout::<3>::new(&[2, 7], &[7, 1], 0).every_nth(1, 3);
+    /// assert_eq!(layout.shape(), &[2, 3]);
+    /// assert_eq!(layout.strides(), &[7, 3]);
+    /// assert_eq!(layout.offset(), 0);
+    /// ```
+    #[inline]
+    pub fn every_nth(&self, axis: usize, n: usize) -> Self {
+        assert!(n > 0, "n must be positive");
+        self.slice_clamped(axis, 0, n as _, usize::MAX)
+    }
+
     /// 一次对多个阶进行切片变换。
-    pub fn slice_many(&self, mut args: &[SliceArg]) -> Self {
-        let content = self.content();
+    pub fn slice_many(&self, args: &[SliceArg]) -> Self {
+        let mut ans = self.clone();
+        ans.slice_many_inplace(args);
+        ans
+    }
+
+    /// 原地进行切片变换，不构造新的布局。
+    ///
+    /// ```rust
+    /// # use ndarray_layout::ArrayLayout;
+    /// let mut layout = ArrayLayout::<3>::new(&[2, 3, 4], &[12, 4, 1], 0);
+    /// layout.slice_inplace(1, 2, -1, 2);
+    /// assert_eq!(layout, ArrayLayout::new(&[2, 3, 4], &[12, 4, 1], 0).slice(1, 2, -1, 2));
+    /// ```
+    #[inline]
+    pub fn slice_inplace(&mut self, axis: usize, start: usize, step: isize, len: usize) {
+        self.slice_many_inplace(&[SliceArg {
+            axis,
+            start,
+            step,
+            len,
+        }])
+    }
+
+    /// 原地对多个阶进行切片变换。
+    pub fn slice_many_inplace(&mut self, mut args: &[SliceArg]) {
+        let ndim = self.ndim;
+        let mut content = self.content_mut();
         let mut offset = content.offset();
-        let iter = zip(content.shape(), content.strides()).enumerate();
 
-        let mut ans = Self::with_ndim(self.ndim);
-        let mut content = ans.content_mut();
-        for (i, (&d, &s)) in iter {
+        for i in 0..ndim {
             match args {
                 [arg, tail @ ..] if arg.axis == i => {
+                    let d = content.shape()[i];
+                    let s = content.strides()[i];
                     let &SliceArg {
                         axis,
                         start,
@@ -74,22 +234,18 @@ impl<const N: usize> ArrayLayout<N> {
 
                     if let [next, ..] = tail {
                         assert!(
-                            axis < next.axis && next.axis < self.ndim,
+                            axis < next.axis && next.axis < ndim,
                             "next.axis = {} !in ({}, {})",
                             next.axis,
                             axis,
-                            self.ndim,
+                            ndim,
                         );
                     }
                     args = tail;
                 }
-                [..] => {
-                    content.set_shape(i, d);
-                    content.set_stride(i, s);
-                }
+                [..] => {}
             }
         }
         content.set_offset(offset as _);
-        ans
     }
 }
@@ -0,0 +1,200 @@
+﻿use super::index::resolve_signed;
+use crate::{ArrayLayout, IndexArg, SliceArg};
+use std::str::FromStr;
+
+/// NumPy 风格的切片表达式，例如 `"::2, 1:5, -1"`。
+///
+/// 表达式由逗号分隔的若干项组成，依次对应张量的各个阶，未指定的阶保持不变。
+/// 每一项是一个整数索引或 `start:stop:step` 形式的切片，整数可以为负数。
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct SliceExpr(Vec<SliceItem>);
+
+/// 切片表达式中的一项。
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum SliceItem {
+    /// 选择一个元素并移除所在的阶。
+    Index(isize),
+    /// 选择一组等间隔的元素。
+    Slice {
+        /// 起始位置，缺省时由步长方向决定。
+        start: Option<isize>,
+        /// 结束位置（不包含），缺省时由步长方向决定。
+        stop: Option<isize>,
+        /// 步长，不能为 0。
+        step: isize,
+    },
+}
+
+/// 切片表达式解析错误。
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum SliceExprError {
+    /// 无法解析的项。
+    InvalidItem(String),
+    /// 步长为 0。
+    ZeroStep(String),
+}
+
+impl SliceExpr {
+    /// 解析切片表达式。
+    ///
+    /// ```rust
+    /// # use ndarray_layout::{SliceExpr, SliceItem};
+    /// let expr = SliceExpr::parse("::2, 1:5, -1").unwrap();
+    /// assert_eq!(
+    ///     expr.items(),
+    ///     &[
+    ///         SliceItem::Slice { start: None, stop: None, step: 2 },
+    ///         SliceItem::Slice { start: Some(1), stop: Some(5), step: 1 },
+    ///         SliceItem::Index(-1),
+    ///     ]
+    /// );
+    /// ```
+    pub fn parse(s: &str) -> Result<Self, SliceExprError> {
+        let s = s.trim();
+        if s.is_empty() {
+            return Ok(Self(vec![]));
+        }
+        s.split(',')
+            .map(parse_item)
+            .collect::<Result<_, _>>()
+            .map(Self)
+    }
+
+    /// 表达式的各项。
+    #[inline]
+    pub fn items(&self) -> &[SliceItem] {
+        &self.0
+    }
+
+    /// 根据张量形状将表达式转换为切片变换参数和索引变换参数。
+    ///
+    /// 先以切片参数进行 [`ArrayLayout::slice_many`]，再以索引参数进行 [`ArrayLayout::index_many`]。
+    pub fn resolve(&self, shape: &[usize]) -> (Vec<SliceArg>, Vec<IndexArg>) {
+        assert!(
+            self.0.len() <= shape.len(),
+            "too many slice items: {} > {}",
+            self.0.len(),
+            shape.len()
+        );
+
+        let mut slices = Vec::new();
+        let mut indices = Vec::new();
+        for (axis, (item, &d)) in self.0.iter().zip(shape).enumerate() {
+            match *item {
+                SliceItem::Index(i) => indices.push(IndexArg {
+                    axis,
+                    index: resolve_signed(d, i),
+                }),
+                SliceItem::Slice { start, stop, step } => {
+                    let d_ = d as isize;
+                    let clamp = |i: isize, lo: isize, hi: isize| {
+                        if i < 0 { i + d_ } else { i }.clamp(lo, hi)
+                    };
+                    let (start, len) = if step > 0 {
+                        let start = start.map_or(0, |i| clamp(i, 0, d_));
+                        let stop = stop.map_or(d_, |i| clamp(i, 0, d_));
+                        (
+                            start,
+                            (stop - start).max(0).unsigned_abs().div_ceil(step as _),
+                        )
+                    } else {
+                        let start = start.map_or(d_ - 1, |i| clamp(i, -1, d_ - 1));
+                        let stop = stop.map_or(-1, |i| clamp(i, -1, d_ - 1));
+                        (
+                            start,
+                            (start - stop).max(0).unsigned_abs().div_ceil(-step as _),
+                        )
+                    };
+                    slices.push(SliceArg {
+                        axis,
+                        start: if len > 0 { start as _ } else { 0 },
+                        step,
+                        len: len as _,
+                    })
+                }
+            }
+        }
+        (slices, indices)
+    }
+}
+
+impl FromStr for SliceExpr {
+    type Err = SliceExprError;
+
+    #[inline]
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+fn parse_item(item: &str) -> Result<SliceItem, SliceExprError> {
+    let item = item.trim();
+    let err = || SliceExprError::InvalidItem(item.into());
+    let int = |s: &str| {
+        let s = s.trim();
+        if s.is_empty() {
+            Ok(None)
+        } else {
+            s.parse().map(Some).map_err(|_| err())
+        }
+    };
+
+    match *item.split(':').collect::<Vec<_>>() {
+        [index] => int(index)?.map(SliceItem::Index).ok_or_else(err),
+        [start, stop] => Ok(SliceItem::Slice {
+            start: int(start)?,
+            stop: int(stop)?,
+            step: 1,
+        }),
+        [start, stop, step] => {
+            let step = int(step)?.unwrap_or(1);
+            if step == 0 {
+                return Err(SliceExprError::ZeroStep(item.into()));
+            }
+            Ok(SliceItem::Slice {
+                start: int(start)?,
+                stop: int(stop)?,
+                step,
+            })
+        }
+        _ => Err(err()),
+    }
+}
+
+impl<const N: usize> ArrayLayout<N> {
+    /// 以切片表达式对张量进行切片和索引变换。
+    ///
+    /// ```rust
+    /// # use ndarray_layout::{ArrayLayout, SliceExpr};
+    /// let expr = SliceExpr::parse("::-1, 1:3, -1").unwrap();
+    /// let layout = ArrayLayout::<3>::new(&[2, 3, 4], &[12, 4, 1], 0).slice_expr(&expr);
+    /// assert_eq!(layout.shape(), &[2, 2]);
+    /// assert_eq!(layout.strides(), &[-12, 4]);
+    /// assert_eq!(layout.offset(), 19);
+    /// ```
+    pub fn slice_expr(&self, expr: &SliceExpr) -> Self {
+        let (slices, indices) = expr.resolve(self.shape());
+        self.slice_many(&slices).index_many(&indices)
+    }
+}
+
+#[test]
+fn test() {
+    assert!(SliceExpr::parse("1:2:3:4").is_err());
+    assert!(SliceExpr::parse("a").is_err());
+    assert!(SliceExpr::parse(":").is_ok());
+    assert_eq!(
+        SliceExpr::parse("::0"),
+        Err(SliceExprError::ZeroStep("::0".into()))
+    );
+
+    let layout = ArrayLayout::<3>::new(&[5], &[1], 0);
+    let layout = layout.slice_expr(&"-2::-2".parse().unwrap());
+    assert_eq!(layout.shape(), &[2]);
+    assert_eq!(layout.strides(), &[-2]);
+    assert_eq!(layout.offset(), 3);
+
+    let layout = ArrayLayout::<3>::new(&[5], &[1], 0);
+    let layout = layout.slice_expr(&"3:1".parse().unwrap());
+    assert_eq!(layout.shape(), &[0]);
+}
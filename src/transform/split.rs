@@ -1,13 +1,34 @@
 ﻿use crate::ArrayLayout;
 
 /// 切分变换参数。
+#[derive(Clone)]
 pub struct Split<'a, const N: usize> {
     src: &'a ArrayLayout<N>,
     axis: usize,
     start: usize,
+    end: usize,
     parts: &'a [usize],
 }
 
+/// 均匀切分变换的迭代器。
+#[derive(Clone)]
+pub struct Chunks<'a, const N: usize> {
+    src: &'a ArrayLayout<N>,
+    axis: usize,
+    start: usize,
+    end: usize,
+    size: usize,
+}
+
+/// 多阶网格切分变换的迭代器。
+#[derive(Clone)]
+pub struct SplitGrid<'a, const N: usize> {
+    src: &'a ArrayLayout<N>,
+    parts: &'a [(usize, &'a [usize])],
+    start: usize,
+    end: usize,
+}
+
 impl<const N: usize> ArrayLayout<N> {
     /// 切分变换讲单个张量沿某个维度切分成多个张量，因此可以支持不均匀的切分。
     ///
@@ -28,12 +49,75 @@ impl<const N: usize> ArrayLayout<N> {
     /// ```
     #[inline]
     pub fn split<'a>(&'a self, axis: usize, parts: &'a [usize]) -> Split<'a, N> {
-        assert_eq!(self.shape()[axis], parts.iter().sum());
+        let end = self.shape()[axis];
+        assert_eq!(end, parts.iter().sum());
         Split {
             src: self,
             axis,
             start: 0,
+            end,
+            parts,
+        }
+    }
+
+    /// 同时沿多个维度切分张量，按行优先顺序产生各个维度切分结果的笛卡尔积。
+    /// 切分的维度必须升序排列。
+    ///
+    /// ```rust
+    /// # use ndarray_layout::ArrayLayout;
+    /// let layout = ArrayLayout::<3>::new(&[4, 6], &[6, 1], 0);
+    /// let tiles = layout.split_grid(&[(0, &[1, 3]), (1, &[2, 4])]).collect::<Vec<_>>();
+    /// assert_eq!(tiles.len(), 4);
+    ///
+    /// assert_eq!(tiles[1].shape(), &[1, 4]);
+    /// assert_eq!(tiles[1].offset(), 2);
+    ///
+    /// assert_eq!(tiles[2].shape(), &[3, 2]);
+    /// assert_eq!(tiles[2].strides(), &[6, 1]);
+    /// assert_eq!(tiles[2].offset(), 6);
+    /// ```
+    pub fn split_grid<'a>(&'a self, parts: &'a [(usize, &'a [usize])]) -> SplitGrid<'a, N> {
+        let shape = self.shape();
+        for (i, &(axis, parts_)) in parts.iter().enumerate() {
+            assert_eq!(shape[axis], parts_.iter().sum());
+            if let Some(&(next, _)) = parts.get(i + 1) {
+                assert!(next > axis, "Split axes must be in ascending order");
+            }
+        }
+        SplitGrid {
+            src: self,
             parts,
+            start: 0,
+            end: parts.iter().map(|(_, parts)| parts.len()).product(),
+        }
+    }
+
+    /// 沿某个维度将张量切分成长度为 `size` 的块，最后一块可能更短。
+    ///
+    /// ```rust
+    /// # use ndarray_layout::ArrayLayout;
+    /// let layout = ArrayLayout::<3>::new(&[2, 3, 5], &[15, 5, 1], 0);
+    /// let chunks = layout.chunks(2, 2).collect::<Vec<_>>();
+    /// assert_eq!(chunks.len(), 3);
+    ///
+    /// assert_eq!(chunks[1].shape(), &[2, 3, 2]);
+    /// assert_eq!(chunks[1].strides(), &[15, 5, 1]);
+    /// assert_eq!(chunks[1].offset(), 2);
+    ///
+    /// assert_eq!(chunks[2].shape(), &[2, 3, 1]);
+    /// assert_eq!(chunks[2].strides(), &[15, 5, 1]);
+    /// assert_eq!(chunks[2].offset(), 4);
+    /// ```
+    #[inline]
+    pub fn chunks(&self, axis: usize, size: usize) -> Chunks<'_, N> {
+        assert!(axis < self.ndim);
+        assert!(size > 0, "chunk size must be positive");
+        Chunks {
+            src: self,
+            axis,
+            start: 0,
+            end: self.shape()[axis],
+            size,
         }
     }
 }
@@ -47,7 +131,209 @@ impl<const N: usize> Iterator for Split<'_, N> {
             let start = self.start;
             self.start += head;
             self.parts = tail;
-            self.src.slice(self.axis, start, 1, head)
+            self.src.split_part(self.axis, start, head)
+        })
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.parts.len(), Some(self.parts.len()))
+    }
+}
+
+impl<const N: usize> DoubleEndedIterator for Split<'_, N> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.parts.split_last().map(|(&last, init)| {
+            self.end -= last;
+            self.parts = init;
+            self.src.split_part(self.axis, self.end, last)
+        })
+    }
+}
+
+impl<const N: usize> ExactSizeIterator for Split<'_, N> {}
+
+impl<const N: usize> Iterator for Chunks<'_, N> {
+    type Item = ArrayLayout<N>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        (self.start < self.end).then(|| {
+            let start = self.start;
+            let len = self.size.min(self.end - start);
+            self.start += len;
+            self.src.split_part(self.axis, start, len)
         })
     }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = (self.end - self.start).div_ceil(self.size);
+        (len, Some(len))
+    }
+}
+
+impl<const N: usize> DoubleEndedIterator for Chunks<'_, N> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        (self.start < self.end).then(|| {
+            let last = self.start + (self.len() - 1) * self.size;
+            let len = self.end - last;
+            self.end = last;
+            self.src.split_part(self.axis, last, len)
+        })
+    }
+}
+
+impl<const N: usize> ExactSizeIterator for Chunks<'_, N> {}
+
+impl<'a, const N: usize> Split<'a, N> {
+    /// 在第 `index` 部分处一分为二。
+    #[cfg(feature = "rayon")]
+    pub(crate) fn split_at(self, index: usize) -> (Self, Self) {
+        let (head, tail) = self.parts.split_at(index);
+        let mid = self.start + head.iter().sum::<usize>();
+        (
+            Self {
+                end: mid,
+                parts: head,
+                ..self.clone()
+            },
+            Self {
+                start: mid,
+                parts: tail,
+                ..self
+            },
+        )
+    }
+}
+
+impl<const N: usize> Chunks<'_, N> {
+    /// 在第 `index` 块处一分为二。
+    #[cfg(feature = "rayon")]
+    pub(crate) fn split_at(self, index: usize) -> (Self, Self) {
+        let mid = (self.start + index * self.size).min(self.end);
+        (
+            Self {
+                end: mid,
+                ..self.clone()
+            },
+            Self { start: mid, ..self },
+        )
+    }
+}
+
+impl<const N: usize> ArrayLayout<N> {
+    /// 选择指定阶上 `[start, start + len)` 的部分，长度为 0 时保持偏移不变。
+    ///
+    /// 切分的参数已经过检查，直接修改元信息，不必构造切片参数。
+    fn split_part(&self, axis: usize, start: usize, len: usize) -> Self {
+        let mut ans = self.clone();
+        if len > 0 {
+            ans.set_offset(self.offset() + start as isize * self.strides()[axis]);
+        }
+        ans.content_mut().set_shape(axis, len);
+        ans
+    }
+}
+
+impl<const N: usize> SplitGrid<'_, N> {
+    /// 按行优先顺序的第 `k` 块。
+    fn tile(&self, mut k: usize) -> ArrayLayout<N> {
+        let mut ans = self.src.clone();
+        let mut offset = ans.offset();
+        let mut content = ans.content_mut();
+        for &(axis, parts) in self.parts.iter().rev() {
+            let j = k % parts.len();
+            k /= parts.len();
+            let len = parts[j];
+            if len > 0 {
+                let start = parts[..j].iter().sum::<usize>();
+                offset += start as isize * self.src.strides()[axis];
+            }
+            content.set_shape(axis, len);
+        }
+        content.set_offset(offset);
+        ans
+    }
+
+    /// 在第 `index` 块处一分为二。
+    #[cfg(feature = "rayon")]
+    pub(crate) fn split_at(self, index: usize) -> (Self, Self) {
+        let mid = self.start + index;
+        (
+            Self {
+                end: mid,
+                ..self.clone()
+            },
+            Self { start: mid, ..self },
+        )
+    }
+}
+
+impl<const N: usize> Iterator for SplitGrid<'_, N> {
+    type Item = ArrayLayout<N>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        (self.start < self.end).then(|| {
+            self.start += 1;
+            self.tile(self.start - 1)
+        })
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.end - self.start;
+        (len, Some(len))
+    }
+}
+
+impl<const N: usize> DoubleEndedIterator for SplitGrid<'_, N> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        (self.start < self.end).then(|| {
+            self.end -= 1;
+            self.tile(self.end)
+        })
+    }
+}
+
+impl<const N: usize> ExactSizeIterator for SplitGrid<'_, N> {}
+
+#[test]
+fn test() {
+    let layout = ArrayLayout::<3>::new(&[2, 6], &[6, 1], 0);
+    let splits = layout.split(1, &[1, 2, 3]);
+    assert_eq!(splits.len(), 3);
+
+    let offsets = splits.clone().rev().map(|l| l.offset()).collect::<Vec<_>>();
+    assert_eq!(offsets, [3, 1, 0]);
+
+    let mut splits = splits;
+    assert_eq!(splits.next_back().unwrap().shape(), &[2, 3]);
+    assert_eq!(splits.next().unwrap().shape(), &[2, 1]);
+    assert_eq!(splits.len(), 1);
+    let last = splits.next().unwrap();
+    assert_eq!(last.shape(), &[2, 2]);
+    assert_eq!(last.offset(), 1);
+    assert!(splits.next_back().is_none());
+
+    // 长度为 0 的部分
+    let layout = ArrayLayout::<3>::new(&[4, 6], &[6, 1], 0);
+    let parts = layout.split(0, &[4, 0]).collect::<Vec<_>>();
+    assert_eq!(parts[1].shape(), &[0, 6]);
+    let tiles = layout.split_grid(&[(0, &[4, 0]), (1, &[2, 4])]);
+    assert_eq!(tiles.len(), 4);
+    let tiles = tiles.collect::<Vec<_>>();
+    assert_eq!(tiles[1], ArrayLayout::new(&[4, 4], &[6, 1], 2));
+    assert_eq!(tiles[3].shape(), &[0, 4]);
+    assert_eq!(tiles[3].offset(), 2);
+    let rev = layout
+        .split_grid(&[(0, &[1, 3]), (1, &[2, 4])])
+        .rev()
+        .map(|l| l.offset())
+        .collect::<Vec<_>>();
+    assert_eq!(rev, [8, 6, 2, 0]);
 }
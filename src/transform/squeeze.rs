@@ -0,0 +1,52 @@
+﻿use crate::{ArrayLayout, IndexArg};
+
+impl<const N: usize> ArrayLayout<N> {
+    /// 压缩变换移除张量所有长度为 1 的阶。
+    ///
+    /// ```rust
+    /// # use ndarray_layout::ArrayLayout;
+    /// let layout = ArrayLayout::<4>::new(&[1, 3, 1, 4], &[12, 4, 4, 1], 0).squeeze();
+    /// assert_eq!(layout.shape(), &[3, 4]);
+    /// assert_eq!(layout.strides(), &[4, 1]);
+    /// assert_eq!(layout.offset(), 0);
+    /// ```
+    pub fn squeeze(&self) -> Self {
+        let content = self.content();
+        let shape = content.shape();
+        let strides = content.strides();
+
+        let mut ans = Self::with_ndim(shape.iter().filter(|&&d| d != 1).count());
+        let mut content = ans.content_mut();
+        content.set_offset(self.offset());
+        let mut j = 0;
+        for (&d, &s) in shape.iter().zip(strides) {
+            if d != 1 {
+                content.set_shape(j, d);
+                content.set_stride(j, s);
+                j += 1;
+            }
+        }
+        ans
+    }
+
+    /// 移除指定的若干长度为 1 的阶，阶号必须升序排列。
+    ///
+    /// ```rust
+    /// # use ndarray_layout::ArrayLayout;
+    /// let layout = ArrayLayout::<4>::new(&[1, 3, 1, 4], &[12, 4, 4, 1], 0).squeeze_axes(&[2]);
+    /// assert_eq!(layout.shape(), &[1, 3, 4]);
+    /// assert_eq!(layout.strides(), &[12, 4, 1]);
+    /// assert_eq!(layout.offset(), 0);
+    /// ```
+    pub fn squeeze_axes(&self, axes: &[usize]) -> Self {
+        let shape = self.shape();
+        let args = axes
+            .iter()
+            .map(|&axis| {
+                assert_eq!(shape[axis], 1, "axis {axis} is not of length 1");
+                IndexArg { axis, index: 0 }
+            })
+            .collect::<Vec<_>>();
+        self.index_many(&args)
+    }
+}
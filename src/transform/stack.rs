@@ -0,0 +1,48 @@
+﻿use crate::{ArrayLayout, AxisIter};
+
+impl<const N: usize> ArrayLayout<N> {
+    /// 计算将若干形状相同的张量在 `axis` 处堆叠为一个新阶后的形状。
+    ///
+    /// ```rust
+    /// # use ndarray_layout::ArrayLayout;
+    /// let a = ArrayLayout::<3>::new(&[3, 4], &[4, 1], 0);
+    /// let b = ArrayLayout::<3>::new(&[3, 4], &[1, 3], 0);
+    /// assert_eq!(ArrayLayout::stack_shape(&[a, b], 1), [3, 2, 4]);
+    /// ```
+    pub fn stack_shape(layouts: &[Self], axis: usize) -> Vec<usize> {
+        let [first, tail @ ..] = layouts else {
+            panic!("stack requires at least one layout")
+        };
+        let shape = first.shape();
+        assert!(axis <= shape.len(), "axis {axis} out of range");
+        for layout in tail {
+            assert_eq!(
+                layout.shape(),
+                shape,
+                "stacked layouts must have the same shape"
+            );
+        }
+
+        let mut ans = Vec::with_capacity(shape.len() + 1);
+        ans.extend_from_slice(&shape[..axis]);
+        ans.push(layouts.len());
+        ans.extend_from_slice(&shape[axis..]);
+        ans
+    }
+
+    /// 沿堆叠的阶拆分堆叠结果，依次产生每个输入张量在结果中对应的目标布局。
+    ///
+    /// ```rust
+    /// # use ndarray_layout::{ArrayLayout, Endian};
+    /// let dst = ArrayLayout::<3>::new_contiguous(&[3, 2, 4], Endian::BigEndian, 1);
+    /// let parts = dst.unstack(1).collect::<Vec<_>>();
+    /// assert_eq!(parts.len(), 2);
+    /// assert_eq!(parts[1].shape(), &[3, 4]);
+    /// assert_eq!(parts[1].strides(), &[8, 1]);
+    /// assert_eq!(parts[1].offset(), 4);
+    /// ```
+    #[inline]
+    pub fn unstack(&self, axis: usize) -> AxisIter<'_, N> {
+        AxisIter::new(self, axis)
+    }
+}
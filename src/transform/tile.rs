@@ -51,6 +51,44 @@ impl<const N: usize> ArrayLayout<N> {
         }])
     }
 
+    /// 按块大小对单个阶进行大端分块，不足一块的剩余部分作为单独的布局返回。
+    ///
+    /// ```rust
+    /// # use ndarray_layout::ArrayLayout;
+    /// let layout = ArrayLayout::<3>::new(&[2, 7], &[7, 1], 0);
+    /// let (body, tail) = layout.tile_blocks(1, 3);
+    /// assert_eq!(body.shape(), &[2, 2, 3]);
+    /// assert_eq!(body.strides(), &[7, 3, 1]);
+    /// assert_eq!(body.offset(), 0);
+    ///
+    /// let tail = tail.unwrap();
+    /// assert_eq!(tail.shape(), &[2, 1]);
+    /// assert_eq!(tail.strides(), &[7, 1]);
+    /// assert_eq!(tail.offset(), 6);
+    ///
+    /// let (body, tail) = layout.slice(1, 0, 1, 0).tile_blocks(1, 3);
+    /// assert_eq!(body.shape(), &[2, 0, 3]);
+    /// assert_eq!(body.strides(), &[7, 3, 1]);
+    /// assert!(tail.is_none());
+    /// ```
+    pub fn tile_blocks(&self, axis: usize, block: usize) -> (Self, Option<Self>) {
+        assert!(block > 0, "block must be positive");
+        let d = self.shape()[axis];
+        let (n, rem) = (d / block, d % block);
+        let body = if d == 0 {
+            // 长度为 0 的阶无法切片，也无法从长度推算步长，先按一块分块再将块数置 0
+            let mut one = self.clone();
+            one.content_mut().set_shape(axis, block);
+            let mut body = one.tile_be(axis, &[1, block]);
+            body.content_mut().set_shape(axis, 0);
+            body
+        } else {
+            self.slice(axis, 0, 1, n * block).tile_be(axis, &[n, block])
+        };
+        let tail = (rem > 0).then(|| self.slice(axis, n * block, 1, rem));
+        (body, tail)
+    }
+
     /// 一次对多个阶进行分块变换。
     pub fn tile_many(&self, mut args: &[TileArg]) -> Self {
         let content = self.content();
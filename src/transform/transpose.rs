@@ -1,5 +1,41 @@
 ﻿use crate::ArrayLayout;
-use std::{collections::BTreeSet, iter::zip};
+use std::{collections::BTreeSet, error::Error, fmt, iter::zip};
+
+/// 置换错误。
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum PermuteError {
+    /// 置换的长度与张量的阶数不同。
+    LengthMismatch {
+        /// 张量的阶数。
+        ndim: usize,
+        /// 置换的长度。
+        len: usize,
+    },
+    /// 置换中的阶超出范围。
+    OutOfRange {
+        /// 超出范围的阶。
+        axis: usize,
+    },
+    /// 置换中的阶重复出现。
+    Duplicate {
+        /// 重复的阶。
+        axis: usize,
+    },
+}
+
+impl fmt::Display for PermuteError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Self::LengthMismatch { ndim, len } => {
+                write!(f, "permutation has {len} axes: expected {ndim}")
+            }
+            Self::OutOfRange { axis } => write!(f, "permutation axis {axis} out of range"),
+            Self::Duplicate { axis } => write!(f, "permutation axis {axis} appears more than once"),
+        }
+    }
+}
+
+impl Error for PermuteError {}
 
 impl<const N: usize> ArrayLayout<N> {
     /// 转置变换允许调换张量的维度顺序，但不改变元素的存储顺序。
@@ -12,9 +48,108 @@ impl<const N: usize> ArrayLayout<N> {
     /// assert_eq!(layout.offset(), 0);
     /// ```
     pub fn transpose(&self, perm: &[usize]) -> Self {
+        let mut ans = self.clone();
+        ans.transpose_inplace(perm);
+        ans
+    }
+
+    /// 原地进行转置变换，不构造新的布局。
+    ///
+    /// ```rust
+    /// # use ndarray_layout::ArrayLayout;
+    /// let mut layout = ArrayLayout::<3>::new(&[2, 3, 4], &[12, 4, 1], 0);
+    /// layout.transpose_inplace(&[2, 0]);
+    /// assert_eq!(layout.shape(), &[4, 3, 2]);
+    /// assert_eq!(layout.strides(), &[1, 4, 12]);
+    /// ```
+    pub fn transpose_inplace(&mut self, perm: &[usize]) {
+        if self.ndim > u64::BITS as usize {
+            return self.transpose_inplace_large(perm);
+        }
+
+        // 以位掩码校验参与转置的阶，第 k 个被选中的阶接收 perm[k] 的元信息
+        let mut mask = 0u64;
+        for &j in perm {
+            assert!(
+                j < self.ndim,
+                "axis {j} out of range for {} dims",
+                self.ndim
+            );
+            assert!(mask & (1 << j) == 0, "axis {j} appears more than once");
+            mask |= 1 << j;
+        }
+        let src = |i: usize| perm[(mask & ((1 << i) - 1)).count_ones() as usize];
+
+        // 沿置换的每个环依次搬移，只需暂存环首的元信息
+        let mut content = self.content_mut();
+        let mut todo = mask;
+        while todo != 0 {
+            let head = todo.trailing_zeros() as usize;
+            let saved = (content.shape()[head], content.strides()[head]);
+            let mut i = head;
+            loop {
+                todo &= !(1 << i);
+                let j = src(i);
+                let (d, s) = if j == head {
+                    saved
+                } else {
+                    (content.shape()[j], content.strides()[j])
+                };
+                content.set_shape(i, d);
+                content.set_stride(i, s);
+                if j == head {
+                    break;
+                }
+                i = j
+            }
+        }
+    }
+
+    /// 阶数超出位掩码容量时的转置变换。
+    fn transpose_inplace_large(&mut self, perm: &[usize]) {
         let perm_ = perm.iter().collect::<BTreeSet<_>>();
         assert_eq!(perm_.len(), perm.len());
 
+        let mut content = self.content_mut();
+        let dims = perm
+            .iter()
+            .map(|&j| (content.shape()[j], content.strides()[j]))
+            .collect::<Vec<_>>();
+        for (&i, (d, s)) in zip(perm_, dims) {
+            content.set_shape(i, d);
+            content.set_stride(i, s);
+        }
+    }
+
+    /// 置换变换按完整的置换 `perm` 重排张量的阶，结果的第 `i` 阶是原张量的第 `perm[i]` 阶。
+    ///
+    /// ```rust
+    /// # use ndarray_layout::{ArrayLayout, PermuteError};
+    /// let layout = ArrayLayout::<3>::new(&[2, 3, 4], &[12, 4, 1], 0);
+    /// let permuted = layout.permute(&[2, 0, 1]).unwrap();
+    /// assert_eq!(permuted.shape(), &[4, 2, 3]);
+    /// assert_eq!(permuted.strides(), &[1, 12, 4]);
+    /// assert_eq!(permuted.offset(), 0);
+    ///
+    /// assert!(matches!(layout.permute(&[1, 0]), Err(PermuteError::LengthMismatch { .. })));
+    /// assert!(matches!(layout.permute(&[1, 1, 0]), Err(PermuteError::Duplicate { axis: 1 })));
+    /// ```
+    pub fn permute(&self, perm: &[usize]) -> Result<Self, PermuteError> {
+        if perm.len() != self.ndim {
+            return Err(PermuteError::LengthMismatch {
+                ndim: self.ndim,
+                len: perm.len(),
+            });
+        }
+        let mut seen = vec![false; self.ndim];
+        for &axis in perm {
+            match seen.get_mut(axis) {
+                None => return Err(PermuteError::OutOfRange { axis }),
+                Some(true) => return Err(PermuteError::Duplicate { axis }),
+                Some(seen) => *seen = true,
+            }
+        }
+
         let content = self.content();
         let shape = content.shape();
         let strides = content.strides();
@@ -22,22 +157,166 @@ impl<const N: usize> ArrayLayout<N> {
         let mut ans = Self::with_ndim(self.ndim);
         let mut content = ans.content_mut();
         content.set_offset(self.offset());
-        let mut set = |i, j| {
+        for (i, &j) in perm.iter().enumerate() {
             content.set_shape(i, shape[j]);
             content.set_stride(i, strides[j]);
-        };
+        }
+        Ok(ans)
+    }
 
-        let mut last = 0;
-        for (&i, &j) in zip(perm_, perm) {
-            for i in last..i {
-                set(i, i);
-            }
-            set(i, j);
-            last = i + 1;
+    /// 转置变换的逆变换，以 `perm` 为参数的 [`transpose`](Self::transpose) 或 [`permute`](Self::permute) 的结果经过此变换恢复原来的阶顺序。
+    ///
+    /// ```rust
+    /// # use ndarray_layout::ArrayLayout;
+    /// let layout = ArrayLayout::<4>::new(&[2, 3, 4, 5], &[60, 20, 5, 1], 0);
+    /// let transposed = layout.transpose(&[3, 1]);
+    /// assert_eq!(transposed.shape(), &[2, 5, 4, 3]);
+    /// assert!(transposed.untranspose(&[3, 1]) == layout);
+    /// ```
+    pub fn untranspose(&self, perm: &[usize]) -> Self {
+        let mut full = (0..self.ndim).collect::<Vec<_>>();
+        let mut sorted = perm.to_vec();
+        sorted.sort_unstable();
+        for (&i, &j) in zip(&sorted, perm) {
+            full[i] = j;
         }
-        for i in last..shape.len() {
-            set(i, i);
+        self.permute(&invert_permutation(&full)).unwrap()
+    }
+
+    /// 规范化变换按步长绝对值从大到小重排张量的阶，同时返回所用的置换。
+    ///
+    /// 步长为 0 的阶排在最后；步长绝对值相同的阶，长度较大的在前；仍无法区分时保持原来的顺序。
+    ///
+    /// ```rust
+    /// # use ndarray_layout::ArrayLayout;
+    /// let layout = ArrayLayout::<4>::new(&[2, 3, 4, 5], &[1, 40, 0, -2], 0);
+    /// let (canonical, perm) = layout.canonical();
+    /// assert_eq!(perm, [1, 3, 0, 2]);
+    /// assert_eq!(canonical.shape(), &[3, 5, 2, 4]);
+    /// assert_eq!(canonical.strides(), &[40, -2, 1, 0]);
+    /// assert_eq!(canonical.offset(), 0);
+    /// ```
+    pub fn canonical(&self) -> (Self, Vec<usize>) {
+        use std::cmp::Reverse;
+
+        let shape = self.shape();
+        let strides = self.strides();
+        let mut perm = (0..self.ndim).collect::<Vec<_>>();
+        perm.sort_by_key(|&i| (Reverse(strides[i].unsigned_abs()), Reverse(shape[i])));
+        (self.permute(&perm).unwrap(), perm)
+    }
+
+    /// 交换张量的两个阶。
+    ///
+    /// ```rust
+    /// # use ndarray_layout::ArrayLayout;
+    /// let layout = ArrayLayout::<3>::new(&[2, 3, 4], &[12, 4, 1], 0).swap_axes(0, 2);
+    /// assert_eq!(layout.shape(), &[4, 3, 2]);
+    /// assert_eq!(layout.strides(), &[1, 4, 12]);
+    /// assert_eq!(layout.offset(), 0);
+    /// ```
+    pub fn swap_axes(&self, a: usize, b: usize) -> Self {
+        let mut ans = self.clone();
+        let mut content = ans.content_mut();
+        let shape = content.shape();
+        let strides = content.strides();
+        let (da, sa) = (shape[a], strides[a]);
+        let (db, sb) = (shape[b], strides[b]);
+        content.set_shape(a, db);
+        content.set_stride(a, sb);
+        content.set_shape(b, da);
+        content.set_stride(b, sa);
+        ans
+    }
+
+    /// 交换张量的最后两阶，即批量矩阵的转置。
+    ///
+    /// ```rust
+    /// # use ndarray_layout::ArrayLayout;
+    /// let layout = ArrayLayout::<3>::new(&[2, 3, 4], &[12, 4, 1], 0).transpose_last_two();
+    /// assert_eq!(layout.shape(), &[2, 4, 3]);
+    /// assert_eq!(layout.strides(), &[12, 1, 4]);
+    /// assert_eq!(layout.offset(), 0);
+    /// ```
+    #[inline]
+    pub fn transpose_last_two(&self) -> Self {
+        assert!(self.ndim >= 2, "matrix transpose requires ndim >= 2");
+        self.swap_axes(self.ndim - 2, self.ndim - 1)
+    }
+
+    /// 将张量的一个阶移动到指定位置，其他阶保持相对顺序。
+    ///
+    /// ```rust
+    /// # use ndarray_layout::ArrayLayout;
+    /// let layout = ArrayLayout::<4>::new(&[2, 3, 4, 5], &[60, 20, 5, 1], 0).move_axis(3, 1);
+    /// assert_eq!(layout.shape(), &[2, 5, 3, 4]);
+    /// assert_eq!(layout.strides(), &[60, 1, 20, 5]);
+    /// assert_eq!(layout.offset(), 0);
+    /// ```
+    pub fn move_axis(&self, from: usize, to: usize) -> Self {
+        assert!(from < self.ndim && to < self.ndim);
+
+        let content = self.content();
+        let shape = content.shape();
+        let strides = content.strides();
+
+        let mut ans = Self::with_ndim(self.ndim);
+        let mut content = ans.content_mut();
+        content.set_offset(self.offset());
+        let mut others = (0..self.ndim).filter(|&i| i != from);
+        for i in 0..self.ndim {
+            let j = if i == to {
+                from
+            } else {
+                others.next().unwrap()
+            };
+            content.set_shape(i, shape[j]);
+            content.set_stride(i, strides[j]);
         }
         ans
     }
 }
+
+/// 计算置换的逆置换。
+///
+/// ```rust
+/// # use ndarray_layout::invert_permutation;
+/// assert_eq!(invert_permutation(&[2, 0, 1]), [1, 2, 0]);
+/// ```
+pub fn invert_permutation(perm: &[usize]) -> Vec<usize> {
+    let mut ans = vec![usize::MAX; perm.len()];
+    for (i, &j) in perm.iter().enumerate() {
+        assert!(
+            ans.get(j).is_some_and(|&k| k == usize::MAX),
+            "invalid permutation: {perm:?}"
+        );
+        ans[j] = i;
+    }
+    ans
+}
+
+#[test]
+fn test() {
+    let layout = ArrayLayout::<2>::new(&[2, 3, 4, 5, 6], &[360, 120, 30, 6, 1], 7);
+    for perm in [
+        &[4, 3, 2, 1, 0][..],
+        &[1, 2, 0],
+        &[4, 0, 3],
+        &[3, 1],
+        &[2],
+        &[],
+    ] {
+        let mut fast = layout.clone();
+        fast.transpose_inplace(perm);
+        let mut slow = layout.clone();
+        slow.transpose_inplace_large(perm);
+        assert_eq!(fast, slow);
+    }
+
+    let ndim = 70;
+    let shape = (1..=ndim).collect::<Vec<_>>();
+    let strides = (1..=ndim as isize).collect::<Vec<_>>();
+    let layout = ArrayLayout::<2>::new(&shape, &strides, 0).transpose(&[69, 0]);
+    assert_eq!(layout.shape()[0], 70);
+    assert_eq!(layout.strides()[69], 1);
+}
@@ -0,0 +1,59 @@
+﻿use crate::ArrayLayout;
+
+impl<const N: usize> ArrayLayout<N> {
+    /// 展开变换将单个阶变换为窗口数和窗口长度两个阶，相邻窗口可以互相重叠。
+    ///
+    /// ```rust
+    /// # use ndarray_layout::ArrayLayout;
+    /// // axis = 1, window = 3, step = 2
+    /// let layout = ArrayLayout::<3>::new(&[2, 7], &[7, 1], 0).unfold(1, 3, 2);
+    /// assert_eq!(layout.shape(), &[2, 3, 3]);
+    /// assert_eq!(layout.strides(), &[7, 2, 1]);
+    /// assert_eq!(layout.offset(), 0);
+    /// ```
+    #[inline]
+    pub fn unfold(&self, axis: usize, window: usize, step: usize) -> Self {
+        self.unfold_dilated(axis, window, step, 1)
+    }
+
+    /// 带膨胀的展开变换，窗口内相邻元素在原阶上间隔 `dilation`。
+    ///
+    /// ```rust
+    /// # use ndarray_layout::ArrayLayout;
+    /// // axis = 1, window = 3, step = 1, dilation = 2
+    /// let layout = ArrayLayout::<3>::new(&[2, 7], &[7, 1], 0).unfold_dilated(1, 3, 1, 2);
+    /// assert_eq!(layout.shape(), &[2, 3, 3]);
+    /// assert_eq!(layout.strides(), &[7, 1, 2]);
+    /// assert_eq!(layout.offset(), 0);
+    /// ```
+    pub fn unfold_dilated(&self, axis: usize, window: usize, step: usize, dilation: usize) -> Self {
+        let content = self.content();
+        let shape = content.shape();
+        let strides = content.strides();
+
+        let d = shape[axis];
+        let s = strides[axis];
+        assert!(step > 0, "step must be positive");
+        assert!(dilation > 0, "dilation must be positive");
+        assert!(window > 0, "window must be positive");
+        let receptive = dilation * (window - 1) + 1;
+        assert!(receptive <= d, "receptive field {receptive} exceeds {d}");
+
+        let mut ans = Self::with_ndim(self.ndim + 1);
+        let mut content = ans.content_mut();
+        content.set_offset(self.offset());
+        for i in 0..axis {
+            content.set_shape(i, shape[i]);
+            content.set_stride(i, strides[i]);
+        }
+        content.set_shape(axis, (d - receptive) / step + 1);
+        content.set_stride(axis, s * step as isize);
+        content.set_shape(axis + 1, window);
+        content.set_stride(axis + 1, s * dilation as isize);
+        for i in axis + 1..self.ndim {
+            content.set_shape(i + 1, shape[i]);
+            content.set_stride(i + 1, strides[i]);
+        }
+        ans
+    }
+}
@@ -0,0 +1,200 @@
+use crate::{ArrayLayout, Endian, LayoutError};
+
+/// 带有元素大小的布局。
+///
+/// 内部以字节为单位存储步长和偏移，并保证它们都是元素大小的整数倍，
+/// 因此可以同时提供以元素和以字节为单位的访问。
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct TypedLayout<const N: usize = 2> {
+    layout: ArrayLayout<N>,
+    element_size: usize,
+}
+
+impl<const N: usize> TypedLayout<N> {
+    /// 为以字节为单位的布局附加元素大小，步长或偏移不是元素大小的整数倍时返回 `None`。
+    ///
+    /// ```rust
+    /// # use ndarray_layout::{ArrayLayout, TypedLayout};
+    /// let layout = ArrayLayout::<3>::new(&[2, 3], &[12, -4], 8);
+    /// let typed = TypedLayout::new(layout.clone(), 4).unwrap();
+    /// assert_eq!(typed.element_strides(), [3, -1]);
+    /// assert_eq!(typed.element_offset(), 2);
+    /// assert_eq!(typed.byte_strides(), &[12, -4]);
+    /// assert!(typed.into_layout() == layout);
+    ///
+    /// assert!(TypedLayout::new(ArrayLayout::<3>::new(&[2], &[6], 0), 4).is_none());
+    /// ```
+    pub fn new(layout: ArrayLayout<N>, element_size: usize) -> Option<Self> {
+        assert!(element_size > 0, "element size must be positive");
+        let size = element_size as isize;
+        let aligned = |n: isize| n % size == 0;
+        (aligned(layout.offset()) && layout.strides().iter().all(|&s| aligned(s))).then_some(Self {
+            layout,
+            element_size,
+        })
+    }
+
+    /// 以元素为单位的步长和偏移构造布局。
+    ///
+    /// ```rust
+    /// # use ndarray_layout::TypedLayout;
+    /// let typed = TypedLayout::<3>::from_elements(&[2, 3], &[3, 1], 1, 8);
+    /// assert_eq!(typed.byte_strides(), &[24, 8]);
+    /// assert_eq!(typed.byte_offset(), 8);
+    /// ```
+    pub fn from_elements(
+        shape: &[usize],
+        strides: &[isize],
+        offset: isize,
+        element_size: usize,
+    ) -> Self {
+        assert!(element_size > 0, "element size must be positive");
+        Self {
+            layout: ArrayLayout::new(shape, strides, offset).to_byte_units(element_size),
+            element_size,
+        }
+    }
+
+    /// 紧密排布的布局，同 [`ArrayLayout::new_contiguous`]。
+    #[inline]
+    pub fn new_contiguous(shape: &[usize], endian: Endian, element_size: usize) -> Self {
+        assert!(element_size > 0, "element size must be positive");
+        Self {
+            layout: ArrayLayout::new_contiguous(shape, endian, element_size),
+            element_size,
+        }
+    }
+
+    /// 元素的字节数。
+    #[inline]
+    pub fn element_size(&self) -> usize {
+        self.element_size
+    }
+
+    /// 以字节为单位的布局。
+    #[inline]
+    pub fn layout(&self) -> &ArrayLayout<N> {
+        &self.layout
+    }
+
+    /// 取出以字节为单位的布局。
+    #[inline]
+    pub fn into_layout(self) -> ArrayLayout<N> {
+        self.layout
+    }
+
+    /// 阶数。
+    #[inline]
+    pub fn ndim(&self) -> usize {
+        self.layout.ndim()
+    }
+
+    /// 形状。
+    #[inline]
+    pub fn shape(&self) -> &[usize] {
+        self.layout.shape()
+    }
+
+    /// 以字节为单位的步长。
+    #[inline]
+    pub fn byte_strides(&self) -> &[isize] {
+        self.layout.strides()
+    }
+
+    /// 以字节为单位的偏移。
+    #[inline]
+    pub fn byte_offset(&self) -> isize {
+        self.layout.offset()
+    }
+
+    /// 以元素为单位的步长。
+    #[inline]
+    pub fn element_strides(&self) -> Vec<isize> {
+        let size = self.element_size as isize;
+        self.layout.strides().iter().map(|&s| s / size).collect()
+    }
+
+    /// 以元素为单位的偏移。
+    #[inline]
+    pub fn element_offset(&self) -> isize {
+        self.layout.offset() / self.element_size as isize
+    }
+
+    /// 对以字节为单位的布局进行变换，保持元素大小不变。
+    ///
+    /// 只进行形状变换的布局步长总是元素大小的整数倍，变换结果不满足时 panic。
+    ///
+    /// ```rust
+    /// # use ndarray_layout::TypedLayout;
+    /// let typed = TypedLayout::<3>::from_elements(&[2, 3], &[3, 1], 0, 4);
+    /// let typed = typed.map(|layout| layout.transpose(&[1, 0]));
+    /// assert_eq!(typed.element_strides(), [1, 3]);
+    /// ```
+    pub fn map(self, f: impl FnOnce(ArrayLayout<N>) -> ArrayLayout<N>) -> Self {
+        let element_size = self.element_size;
+        Self::new(f(self.layout), element_size).expect("transform breaks element alignment")
+    }
+}
+
+impl<const N: usize> ArrayLayout<N> {
+    /// 将以字节为单位的步长和偏移转换为以元素为单位。
+    ///
+    /// ```rust
+    /// # use ndarray_layout::{ArrayLayout, LayoutError};
+    /// let layout = ArrayLayout::<3>::new(&[2, 3], &[12, -4], 8);
+    /// let elements = layout.to_element_units(4).unwrap();
+    /// assert_eq!(elements.strides(), &[3, -1]);
+    /// assert_eq!(elements.offset(), 2);
+    /// assert_eq!(elements.to_byte_units(4), layout);
+    ///
+    /// assert_eq!(
+    ///     layout.to_element_units(8),
+    ///     Err(LayoutError::MisalignedStride { axis: 0, stride: 12, element_size: 8 })
+    /// );
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// `element_size` 为 0 时 panic。
+    pub fn to_element_units(&self, element_size: usize) -> Result<Self, LayoutError> {
+        assert!(element_size > 0, "element size must be positive");
+        let size = element_size as isize;
+        let content = self.content();
+        let offset = content.offset();
+        if offset % size != 0 {
+            return Err(LayoutError::MisalignedOffset {
+                offset,
+                element_size,
+            });
+        }
+        let mut ans = Self::with_ndim(self.ndim);
+        let mut target = ans.content_mut();
+        target.set_offset(offset / size);
+        target.copy_shape(content.shape());
+        for (axis, &stride) in content.strides().iter().enumerate() {
+            if stride % size != 0 {
+                return Err(LayoutError::MisalignedStride {
+                    axis,
+                    stride,
+                    element_size,
+                });
+            }
+            target.set_stride(axis, stride / size);
+        }
+        Ok(ans)
+    }
+
+    /// 将以元素为单位的步长和偏移转换为以字节为单位。
+    pub fn to_byte_units(&self, element_size: usize) -> Self {
+        let size = element_size as isize;
+        let content = self.content();
+        let mut ans = Self::with_ndim(self.ndim);
+        let mut target = ans.content_mut();
+        target.set_offset(content.offset() * size);
+        target.copy_shape(content.shape());
+        for (axis, &stride) in content.strides().iter().enumerate() {
+            target.set_stride(axis, stride * size);
+        }
+        ans
+    }
+}